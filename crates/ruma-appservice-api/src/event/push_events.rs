@@ -135,6 +135,51 @@ pub mod v1 {
                 to_device: Vec::new(),
             }
         }
+
+        /// Splits the given events into several `Request`s, preserving their order.
+        ///
+        /// Each request contains at most `max_events` events, and the sum of the sizes of their
+        /// JSON representations is at most `max_size` bytes. An event that is larger than
+        /// `max_size` on its own is sent alone in a request.
+        ///
+        /// `next_txn_id` is called to get the transaction ID of each request, in the order in
+        /// which the requests must be sent.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `max_events` is `0`.
+        pub fn batches(
+            events: Vec<Raw<AnyTimelineEvent>>,
+            max_events: usize,
+            max_size: usize,
+            mut next_txn_id: impl FnMut() -> OwnedTransactionId,
+        ) -> Vec<Request> {
+            assert!(max_events > 0, "max_events must be greater than 0");
+
+            let mut requests = Vec::new();
+            let mut batch = Vec::new();
+            let mut batch_size = 0;
+
+            for event in events {
+                let event_size = event.json().get().len();
+
+                if !batch.is_empty()
+                    && (batch.len() == max_events || batch_size + event_size > max_size)
+                {
+                    requests.push(Request::new(next_txn_id(), std::mem::take(&mut batch)));
+                    batch_size = 0;
+                }
+
+                batch_size += event_size;
+                batch.push(event);
+            }
+
+            if !batch.is_empty() {
+                requests.push(Request::new(next_txn_id(), batch));
+            }
+
+            requests
+        }
     }
 
     impl Response {
@@ -389,7 +434,10 @@ pub mod v1 {
     #[cfg(feature = "server")]
     #[cfg(test)]
     mod tests {
-        use ruma_common::api::{OutgoingRequest, SendAccessToken};
+        use ruma_common::{
+            api::{OutgoingRequest, SendAccessToken},
+            serde::Raw,
+        };
         use serde_json::json;
 
         use super::Request;
@@ -424,5 +472,32 @@ pub mod v1 {
                 json_body.as_object().unwrap().get("events").unwrap().as_array().unwrap().len()
             );
         }
+
+        #[test]
+        fn batches() {
+            let events: Vec<_> = (0..5)
+                .map(|i| Raw::new(&json!({ "type": "m.room.message", "i": i })).unwrap().cast())
+                .collect();
+            let event_size = events[0].json().get().len();
+
+            let mut txn_ids = (0..).map(|i| format!("txn{i}").into());
+            let requests =
+                Request::batches(events.clone(), 2, usize::MAX, || txn_ids.next().unwrap());
+            assert_eq!(requests.len(), 3);
+            assert_eq!(requests[0].txn_id, "txn0");
+            assert_eq!(requests[2].txn_id, "txn2");
+            assert_eq!(requests[0].events.len(), 2);
+            assert_eq!(requests[2].events.len(), 1);
+            assert_eq!(requests[2].events[0].json().get(), events[4].json().get());
+
+            let requests = Request::batches(events.clone(), 10, event_size * 3, || "txn".into());
+            assert_eq!(requests.len(), 2);
+            assert_eq!(requests[0].events.len(), 3);
+            assert_eq!(requests[1].events.len(), 2);
+
+            // Events larger than the maximum size are sent alone.
+            let requests = Request::batches(events, 10, 1, || "txn".into());
+            assert_eq!(requests.len(), 5);
+        }
     }
 }
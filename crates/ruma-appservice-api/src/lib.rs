@@ -7,6 +7,8 @@
 
 #![warn(missing_docs)]
 
+use regex::Regex;
+use ruma_common::{RoomAliasId, RoomId, ServerName, UserId};
 use serde::{Deserialize, Serialize};
 
 pub mod event;
@@ -19,18 +21,53 @@ pub mod thirdparty;
 /// Used for [appservice registration](https://spec.matrix.org/latest/application-service-api/#registration).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+#[serde(try_from = "NamespaceSerdeHelper")]
 pub struct Namespace {
     /// Whether this application service has exclusive access to events within this namespace.
     pub exclusive: bool,
 
     /// A regular expression defining which values this namespace includes.
-    pub regex: String,
+    regex: String,
+
+    /// The compiled `regex`, anchored at the beginning of the string.
+    #[serde(skip_serializing)]
+    compiled_regex: Regex,
 }
 
 impl Namespace {
     /// Creates a new `Namespace` with the given exclusivity and regex pattern.
-    pub fn new(exclusive: bool, regex: String) -> Self {
-        Namespace { exclusive, regex }
+    ///
+    /// Returns an error if the regex is invalid.
+    pub fn new(exclusive: bool, regex: String) -> Result<Self, regex::Error> {
+        let compiled_regex = Regex::new(&format!("^(?:{regex})"))?;
+        Ok(Namespace { exclusive, regex, compiled_regex })
+    }
+
+    /// The regular expression defining which values this namespace includes.
+    pub fn regex(&self) -> &str {
+        &self.regex
+    }
+
+    /// Whether the given string matches the regex of this namespace.
+    ///
+    /// Like in the reference homeserver implementation, the regex only needs to match at the
+    /// beginning of the string.
+    pub fn matches(&self, value: &str) -> bool {
+        self.compiled_regex.is_match(value)
+    }
+}
+
+#[derive(Deserialize)]
+struct NamespaceSerdeHelper {
+    exclusive: bool,
+    regex: String,
+}
+
+impl TryFrom<NamespaceSerdeHelper> for Namespace {
+    type Error = regex::Error;
+
+    fn try_from(helper: NamespaceSerdeHelper) -> Result<Self, Self::Error> {
+        Self::new(helper.exclusive, helper.regex)
     }
 }
 
@@ -59,6 +96,43 @@ impl Namespaces {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Whether the given user ID is in one of the `users` namespaces.
+    pub fn matches_user_id(&self, user_id: &UserId) -> bool {
+        matches_any(&self.users, user_id.as_str(), false)
+    }
+
+    /// Whether the given user ID is in one of the exclusive `users` namespaces.
+    pub fn exclusively_matches_user_id(&self, user_id: &UserId) -> bool {
+        matches_any(&self.users, user_id.as_str(), true)
+    }
+
+    /// Whether the given room alias is in one of the `aliases` namespaces.
+    pub fn matches_room_alias(&self, room_alias: &RoomAliasId) -> bool {
+        matches_any(&self.aliases, room_alias.as_str(), false)
+    }
+
+    /// Whether the given room alias is in one of the exclusive `aliases` namespaces.
+    pub fn exclusively_matches_room_alias(&self, room_alias: &RoomAliasId) -> bool {
+        matches_any(&self.aliases, room_alias.as_str(), true)
+    }
+
+    /// Whether the given room ID is in one of the `rooms` namespaces.
+    pub fn matches_room_id(&self, room_id: &RoomId) -> bool {
+        matches_any(&self.rooms, room_id.as_str(), false)
+    }
+
+    /// Whether the given room ID is in one of the exclusive `rooms` namespaces.
+    pub fn exclusively_matches_room_id(&self, room_id: &RoomId) -> bool {
+        matches_any(&self.rooms, room_id.as_str(), true)
+    }
+}
+
+/// Whether the given value matches any of the given namespaces.
+///
+/// If `exclusive_only` is `true`, only exclusive namespaces are checked.
+fn matches_any(namespaces: &[Namespace], value: &str, exclusive_only: bool) -> bool {
+    namespaces.iter().any(|ns| (ns.exclusive || !exclusive_only) && ns.matches(value))
 }
 
 /// Information required in the registration yaml file that a homeserver needs.
@@ -139,6 +213,22 @@ pub struct RegistrationInit {
     pub protocols: Option<Vec<String>>,
 }
 
+impl Registration {
+    /// Whether the application service is allowed to act as the given user.
+    ///
+    /// This is the case for the user of the application service itself, identified by
+    /// `sender_localpart`, and for the users in its `users` namespaces, if they belong to the
+    /// homeserver with the given server name.
+    ///
+    /// Homeservers should check this before allowing an application service to log in or
+    /// register a user, or to masquerade as a user with the `user_id` query parameter.
+    pub fn is_user_id_allowed(&self, user_id: &UserId, server_name: &ServerName) -> bool {
+        user_id.server_name() == server_name
+            && (user_id.localpart() == self.sender_localpart
+                || self.namespaces.matches_user_id(user_id))
+    }
+}
+
 impl From<RegistrationInit> for Registration {
     fn from(init: RegistrationInit) -> Self {
         let RegistrationInit {
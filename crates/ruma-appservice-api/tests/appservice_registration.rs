@@ -1,5 +1,6 @@
 use assert_matches2::assert_matches;
 use ruma_appservice_api::Registration;
+use ruma_common::{room_alias_id, room_id, server_name, user_id};
 
 #[test]
 fn registration_deserialization() {
@@ -36,11 +37,11 @@ fn registration_deserialization() {
 
     assert_eq!(observed.namespaces.users.len(), 1);
     assert!(observed.namespaces.users[0].exclusive);
-    assert_eq!(observed.namespaces.users[0].regex, "@_irc_bridge_.*");
+    assert_eq!(observed.namespaces.users[0].regex(), "@_irc_bridge_.*");
 
     assert_eq!(observed.namespaces.aliases.len(), 1);
     assert!(!observed.namespaces.aliases[0].exclusive);
-    assert_eq!(observed.namespaces.aliases[0].regex, "#_irc_bridge_.*");
+    assert_eq!(observed.namespaces.aliases[0].regex(), "#_irc_bridge_.*");
 
     assert_eq!(observed.namespaces.rooms.len(), 0);
 }
@@ -61,3 +62,86 @@ fn config_with_optional_url() {
     assert_matches!(serde_yaml::from_str(registration_config).unwrap(), Registration { url, .. });
     assert_eq!(url, None);
 }
+
+#[test]
+fn namespaces_matching() {
+    let registration_config = r##"
+        id: "IRC Bridge"
+        url: "http://127.0.0.1:1234"
+        as_token: "30c05ae90a248a4188e620216fa72e349803310ec83e2a77b34fe90be6081f46"
+        hs_token: "312df522183efd404ec1cd22d2ffa4bbc76a8c1ccf541dd692eef281356bb74e"
+        sender_localpart: "_irc_bot"
+        namespaces:
+          users:
+            - exclusive: true
+              regex: "@_irc_bridge_.*:example\\.org"
+            - exclusive: false
+              regex: "@_discord_\\d+:example\\.org"
+          aliases:
+            - exclusive: false
+              regex: "#_irc_bridge_.*"
+            - exclusive: false
+              regex: "(?i)#_DISCORD_.*"
+          rooms:
+            - exclusive: false
+              regex: "!irc_.*:example\\.org"
+        "##;
+    let namespaces = serde_yaml::from_str::<Registration>(registration_config).unwrap().namespaces;
+
+    assert!(namespaces.matches_user_id(user_id!("@_irc_bridge_alice:example.org")));
+    assert!(namespaces.exclusively_matches_user_id(user_id!("@_irc_bridge_alice:example.org")));
+    assert!(!namespaces.matches_user_id(user_id!("@_irc_bridge_alice:example.com")));
+    assert!(!namespaces.matches_user_id(user_id!("@alice_irc_bridge_alice:example.org")));
+    assert!(namespaces.matches_user_id(user_id!("@_discord_1234:example.org")));
+    assert!(!namespaces.exclusively_matches_user_id(user_id!("@_discord_1234:example.org")));
+    assert!(!namespaces.matches_user_id(user_id!("@_discord_alice:example.org")));
+
+    assert!(namespaces.matches_room_alias(room_alias_id!("#_irc_bridge_room:example.org")));
+    assert!(
+        !namespaces.exclusively_matches_room_alias(room_alias_id!("#_irc_bridge_room:example.org"))
+    );
+    assert!(namespaces.matches_room_alias(room_alias_id!("#_discord_room:example.org")));
+    assert!(!namespaces.matches_room_alias(room_alias_id!("#room:example.org")));
+
+    assert!(namespaces.matches_room_id(room_id!("!irc_room:example.org")));
+    assert!(!namespaces.exclusively_matches_room_id(room_id!("!irc_room:example.org")));
+    assert!(!namespaces.matches_room_id(room_id!("!room:example.org")));
+}
+
+#[test]
+fn namespace_with_invalid_regex() {
+    let registration_config = r##"
+        id: "IRC Bridge"
+        url: "http://127.0.0.1:1234"
+        as_token: "30c05ae90a248a4188e620216fa72e349803310ec83e2a77b34fe90be6081f46"
+        hs_token: "312df522183efd404ec1cd22d2ffa4bbc76a8c1ccf541dd692eef281356bb74e"
+        sender_localpart: "_irc_bot"
+        namespaces:
+          rooms:
+            - exclusive: false
+              regex: "[invalid"
+        "##;
+    serde_yaml::from_str::<Registration>(registration_config).unwrap_err();
+}
+
+#[test]
+fn user_id_allowed() {
+    let registration_config = r##"
+        id: "IRC Bridge"
+        url: "http://127.0.0.1:1234"
+        as_token: "30c05ae90a248a4188e620216fa72e349803310ec83e2a77b34fe90be6081f46"
+        hs_token: "312df522183efd404ec1cd22d2ffa4bbc76a8c1ccf541dd692eef281356bb74e"
+        sender_localpart: "_irc_bot"
+        namespaces:
+          users:
+            - exclusive: true
+              regex: "@_irc_bridge_.*:example\\.org"
+        "##;
+    let registration = serde_yaml::from_str::<Registration>(registration_config).unwrap();
+    let server_name = server_name!("example.org");
+
+    assert!(registration.is_user_id_allowed(user_id!("@_irc_bot:example.org"), server_name));
+    assert!(registration.is_user_id_allowed(user_id!("@_irc_bridge_bob:example.org"), server_name));
+    assert!(!registration.is_user_id_allowed(user_id!("@bob:example.org"), server_name));
+    assert!(!registration.is_user_id_allowed(user_id!("@_irc_bot:example.com"), server_name));
+}
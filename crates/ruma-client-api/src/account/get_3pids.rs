@@ -10,7 +10,7 @@ pub mod v3 {
     use ruma_common::{
         api::{request, response, Metadata},
         metadata,
-        thirdparty::ThirdPartyIdentifier,
+        thirdparty::{Medium, ThirdPartyIdentifier},
     };
 
     const METADATA: Metadata = metadata! {
@@ -56,5 +56,16 @@ pub mod v3 {
         pub fn new(threepids: Vec<ThirdPartyIdentifier>) -> Self {
             Self { threepids }
         }
+
+        /// Returns an iterator over the third party identifiers with the given medium.
+        ///
+        /// This can be used to list the email addresses or the phone numbers associated with the
+        /// account separately.
+        pub fn threepids_with_medium<'a>(
+            &'a self,
+            medium: &'a Medium,
+        ) -> impl Iterator<Item = &'a ThirdPartyIdentifier> + 'a {
+            self.threepids.iter().filter(move |threepid| threepid.medium == *medium)
+        }
     }
 }
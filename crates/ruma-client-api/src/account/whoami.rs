@@ -16,6 +16,7 @@ pub mod v3 {
         method: GET,
         rate_limited: true,
         authentication: AccessToken,
+        guest_access: true,
         history: {
             1.0 => "/_matrix/client/r0/account/whoami",
             1.1 => "/_matrix/client/v3/account/whoami",
@@ -57,4 +57,67 @@ pub mod v3 {
             Self {}
         }
     }
+
+    #[cfg(all(test, feature = "client"))]
+    mod client_tests {
+        use ruma_common::{
+            api::{MatrixVersion, OutgoingRequest, SendAccessToken},
+            owned_room_id,
+        };
+        use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+        use super::Request;
+        use crate::room::Visibility;
+
+        #[test]
+        fn serialize_request() {
+            let req = Request::new(
+                "irc.example.org".to_owned(),
+                owned_room_id!("!portal:example.org"),
+                Visibility::Public,
+            )
+            .try_into_http_request::<Vec<u8>>(
+                "https://homeserver.tld",
+                SendAccessToken::IfRequired("as_token"),
+                &[MatrixVersion::V1_1],
+            )
+            .unwrap();
+
+            assert_eq!(req.method(), http::Method::PUT);
+            assert_eq!(
+                req.uri(),
+                "https://homeserver.tld/_matrix/client/v3/directory/list/appservice/irc.example.org/!portal:example.org"
+            );
+            assert_eq!(
+                from_json_slice::<JsonValue>(req.body()).unwrap(),
+                json!({ "visibility": "public" })
+            );
+        }
+    }
+
+    #[cfg(all(test, feature = "server"))]
+    mod server_tests {
+        use ruma_common::api::IncomingRequest as _;
+        use serde_json::{json, to_vec as to_json_vec};
+
+        use super::Request;
+        use crate::room::Visibility;
+
+        #[test]
+        fn deserialize_request() {
+            let req = Request::try_from_http_request(
+                http::Request::builder()
+                    .method("PUT")
+                    .uri("https://homeserver.tld/_matrix/client/v3/directory/list/appservice/irc.example.org/!portal:example.org")
+                    .body(to_json_vec(&json!({ "visibility": "private" })).unwrap())
+                    .unwrap(),
+                &["irc.example.org", "!portal:example.org"],
+            )
+            .unwrap();
+
+            assert_eq!(req.network_id, "irc.example.org");
+            assert_eq!(req.room_id, "!portal:example.org");
+            assert_eq!(req.visibility, Visibility::Private);
+        }
+    }
 }
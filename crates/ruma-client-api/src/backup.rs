@@ -15,14 +15,23 @@ pub mod get_backup_keys_for_session;
 pub mod get_latest_backup_info;
 pub mod update_backup_version;
 
+#[cfg(feature = "backup-crypto")]
+mod crypto;
+
 use std::collections::BTreeMap;
 
 use js_int::UInt;
 use ruma_common::{
-    serde::{Base64, Raw},
-    OwnedDeviceKeyId, OwnedUserId,
+    serde::{from_raw_json_value, Base64, Raw},
+    KeyBackupAlgorithm, OwnedDeviceKeyId, OwnedUserId,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::value::RawValue as RawJsonValue;
+
+#[cfg(feature = "backup-crypto")]
+pub use self::crypto::{
+    BackedUpSessionData, BackupDecryptionError, BackupRecoveryKey, BackupSignatureError,
 };
-use serde::{Deserialize, Serialize};
 
 /// A wrapper around a mapping of session IDs to key data.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -40,12 +49,10 @@ impl RoomKeyBackup {
 }
 
 /// The algorithm used for storing backups.
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(tag = "algorithm", content = "auth_data")]
+#[derive(Clone, Debug)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub enum BackupAlgorithm {
     /// `m.megolm_backup.v1.curve25519-aes-sha2` backup algorithm.
-    #[serde(rename = "m.megolm_backup.v1.curve25519-aes-sha2")]
     MegolmBackupV1Curve25519AesSha2 {
         /// The curve25519 public key used to encrypt the backups, encoded in unpadded base64.
         public_key: Base64,
@@ -55,6 +62,110 @@ pub enum BackupAlgorithm {
     },
 }
 
+impl BackupAlgorithm {
+    /// The algorithm used for the backup.
+    pub fn algorithm(&self) -> KeyBackupAlgorithm {
+        match self {
+            Self::MegolmBackupV1Curve25519AesSha2 { .. } => {
+                KeyBackupAlgorithm::MegolmBackupV1Curve25519AesSha2
+            }
+        }
+    }
+
+    /// The auth data of this algorithm, if it is `m.megolm_backup.v1.curve25519-aes-sha2`.
+    pub fn megolm_backup_v1_auth_data(&self) -> Option<MegolmBackupV1Curve25519AesSha2AuthData> {
+        match self {
+            Self::MegolmBackupV1Curve25519AesSha2 { public_key, signatures } => {
+                Some(MegolmBackupV1Curve25519AesSha2AuthData {
+                    public_key: public_key.clone(),
+                    signatures: signatures.clone(),
+                })
+            }
+        }
+    }
+}
+
+impl From<MegolmBackupV1Curve25519AesSha2AuthData> for BackupAlgorithm {
+    fn from(auth_data: MegolmBackupV1Curve25519AesSha2AuthData) -> Self {
+        let MegolmBackupV1Curve25519AesSha2AuthData { public_key, signatures } = auth_data;
+        Self::MegolmBackupV1Curve25519AesSha2 { public_key, signatures }
+    }
+}
+
+#[derive(Serialize)]
+struct BackupAlgorithmSerHelper<T> {
+    algorithm: KeyBackupAlgorithm,
+    auth_data: T,
+}
+
+#[derive(Serialize)]
+struct MegolmBackupV1Curve25519AesSha2AuthDataSerHelper<'a> {
+    public_key: &'a Base64,
+    signatures: &'a BTreeMap<OwnedUserId, BTreeMap<OwnedDeviceKeyId, String>>,
+}
+
+impl Serialize for BackupAlgorithm {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let algorithm = self.algorithm();
+        match self {
+            Self::MegolmBackupV1Curve25519AesSha2 { public_key, signatures } => {
+                let auth_data =
+                    MegolmBackupV1Curve25519AesSha2AuthDataSerHelper { public_key, signatures };
+                BackupAlgorithmSerHelper { algorithm, auth_data }.serialize(serializer)
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct BackupAlgorithmDeHelper {
+    algorithm: KeyBackupAlgorithm,
+    auth_data: Box<RawJsonValue>,
+}
+
+impl<'de> Deserialize<'de> for BackupAlgorithm {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let BackupAlgorithmDeHelper { algorithm, auth_data } =
+            BackupAlgorithmDeHelper::deserialize(deserializer)?;
+
+        match algorithm {
+            KeyBackupAlgorithm::MegolmBackupV1Curve25519AesSha2 => {
+                from_raw_json_value::<MegolmBackupV1Curve25519AesSha2AuthData, _>(&auth_data)
+                    .map(Into::into)
+            }
+            _ => Err(de::Error::custom(format!("unknown backup algorithm `{algorithm}`"))),
+        }
+    }
+}
+
+/// The auth data of the `m.megolm_backup.v1.curve25519-aes-sha2` backup algorithm.
+///
+/// With the `backup-crypto` feature, its signatures can be verified against the keys of a device
+/// or a cross-signing key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct MegolmBackupV1Curve25519AesSha2AuthData {
+    /// The curve25519 public key used to encrypt the backups, encoded in unpadded base64.
+    pub public_key: Base64,
+
+    /// Signatures of the auth_data as Signed JSON.
+    pub signatures: BTreeMap<OwnedUserId, BTreeMap<OwnedDeviceKeyId, String>>,
+}
+
+impl MegolmBackupV1Curve25519AesSha2AuthData {
+    /// Creates a new `MegolmBackupV1Curve25519AesSha2AuthData` with the given public key and no
+    /// signatures.
+    pub fn new(public_key: Base64) -> Self {
+        Self { public_key, signatures: BTreeMap::new() }
+    }
+}
+
 /// Information about the backup key.
 ///
 /// To create an instance of this type, first create a [`KeyBackupDataInit`] and convert it via
@@ -143,3 +254,41 @@ impl From<EncryptedSessionDataInit> for EncryptedSessionData {
         Self { ephemeral, ciphertext, mac }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_matches2::assert_matches;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::BackupAlgorithm;
+
+    #[test]
+    fn backup_algorithm_serde() {
+        let json = json!({
+            "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+            "auth_data": {
+                "public_key": "hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo",
+                "signatures": {
+                    "@alice:example.org": {
+                        "ed25519:DEVICEID": "signature",
+                    },
+                },
+            },
+        });
+
+        let algorithm = from_json_value::<BackupAlgorithm>(json.clone()).unwrap();
+        assert_matches!(
+            &algorithm,
+            BackupAlgorithm::MegolmBackupV1Curve25519AesSha2 { public_key, signatures }
+        );
+        assert_eq!(public_key.encode(), "hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo");
+        assert_eq!(signatures.len(), 1);
+        assert_eq!(to_json_value(&algorithm).unwrap(), json);
+
+        let unknown = json!({
+            "algorithm": "io.ruma.backup",
+            "auth_data": {},
+        });
+        from_json_value::<BackupAlgorithm>(unknown).unwrap_err();
+    }
+}
@@ -0,0 +1,434 @@
+//! Cryptographic operations of the `m.megolm_backup.v1.curve25519-aes-sha2` backup algorithm.
+
+use std::{collections::BTreeMap, fmt};
+
+use aes::{
+    cipher::{block_padding::Pkcs7, BlockDecryptMut, KeyIvInit},
+    Aes256,
+};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use ruma_common::{
+    canonical_json::to_canonical_value,
+    encryption::{CrossSigningKey, DeviceKeys},
+    serde::Base64,
+    CanonicalJsonValue, DeviceKeyAlgorithm, DeviceKeyId, EventEncryptionAlgorithm,
+    OwnedDeviceKeyId, UserId,
+};
+use ruma_events::secret_storage::recovery_key::{RecoveryKey, RecoveryKeyError};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use super::{EncryptedSessionData, MegolmBackupV1Curve25519AesSha2AuthData};
+
+impl MegolmBackupV1Curve25519AesSha2AuthData {
+    /// Verify the signature of this auth data by the given ed25519 key of the given user.
+    ///
+    /// Other signatures of the auth data are ignored.
+    pub fn verify_signature(
+        &self,
+        user_id: &UserId,
+        key_id: &DeviceKeyId,
+        public_key: &Base64,
+    ) -> Result<(), BackupSignatureError> {
+        let signature = self
+            .signatures
+            .get(user_id)
+            .and_then(|signatures| signatures.get(key_id))
+            .ok_or(BackupSignatureError::MissingSignature)?;
+
+        let mut object = match to_canonical_value(self) {
+            Ok(CanonicalJsonValue::Object(object)) => object,
+            _ => unreachable!("auth data is always serialized as a canonical JSON object"),
+        };
+
+        // Only keep the signature to verify, since `verify_json` requires a public key for every
+        // signature.
+        let signatures = BTreeMap::from([(
+            user_id.to_string(),
+            CanonicalJsonValue::Object(BTreeMap::from([(
+                key_id.to_string(),
+                CanonicalJsonValue::String(signature.clone()),
+            )])),
+        )]);
+        object.insert("signatures".to_owned(), CanonicalJsonValue::Object(signatures));
+
+        let public_key_map = BTreeMap::from([(
+            user_id.to_string(),
+            BTreeMap::from([(key_id.to_string(), public_key.clone())]),
+        )]);
+
+        Ok(ruma_signatures::verify_json(&public_key_map, &object)?)
+    }
+
+    /// Verify the signature of this auth data by the ed25519 key of the given device.
+    pub fn verify_device_signature(
+        &self,
+        device_keys: &DeviceKeys,
+    ) -> Result<(), BackupSignatureError> {
+        self.verify_signature_with_keys(&device_keys.user_id, &device_keys.keys)
+    }
+
+    /// Verify the signature of this auth data by the given cross-signing key.
+    ///
+    /// The key is usually the master key of the user that created the backup.
+    pub fn verify_cross_signing_signature(
+        &self,
+        key: &CrossSigningKey,
+    ) -> Result<(), BackupSignatureError> {
+        self.verify_signature_with_keys(&key.user_id, &key.keys)
+    }
+
+    fn verify_signature_with_keys(
+        &self,
+        user_id: &UserId,
+        keys: &BTreeMap<OwnedDeviceKeyId, String>,
+    ) -> Result<(), BackupSignatureError> {
+        let (key_id, public_key) = keys
+            .iter()
+            .find(|(key_id, _)| key_id.algorithm() == DeviceKeyAlgorithm::Ed25519)
+            .ok_or(BackupSignatureError::MissingPublicKey)?;
+        let public_key =
+            Base64::parse(public_key).map_err(|_| BackupSignatureError::InvalidPublicKey)?;
+
+        self.verify_signature(user_id, key_id, &public_key)
+    }
+}
+
+/// An error encountered when verifying the signature of backup auth data.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum BackupSignatureError {
+    /// The keys don't contain an ed25519 public key.
+    #[error("no ed25519 public key found")]
+    MissingPublicKey,
+
+    /// The ed25519 public key is not valid base64.
+    #[error("the ed25519 public key is not valid base64")]
+    InvalidPublicKey,
+
+    /// The auth data is not signed by the key.
+    #[error("no signature found for the key")]
+    MissingSignature,
+
+    /// The signature is invalid.
+    #[error(transparent)]
+    Verification(#[from] ruma_signatures::Error),
+}
+
+/// The private key of a `m.megolm_backup.v1.curve25519-aes-sha2` backup.
+///
+/// It is used to decrypt the room keys in the backup, and is usually shown to the user encoded as
+/// a [recovery key]. The key is zeroized when this is dropped.
+///
+/// [recovery key]: https://spec.matrix.org/latest/client-server-api/#recovery-key
+pub struct BackupRecoveryKey {
+    secret: StaticSecret,
+}
+
+impl BackupRecoveryKey {
+    /// Creates a `BackupRecoveryKey` from the bytes of the curve25519 private key.
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self { secret: StaticSecret::from(bytes) }
+    }
+
+    /// Parses a `BackupRecoveryKey` from its recovery key encoding.
+    ///
+    /// Whitespace in the recovery key is ignored.
+    pub fn from_base58(recovery_key: &str) -> Result<Self, RecoveryKeyError> {
+        Ok(RecoveryKey::from_base58(recovery_key)?.into())
+    }
+
+    /// Encodes this key as a recovery key.
+    ///
+    /// The returned string is split in groups of 4 characters separated by spaces, to make it
+    /// easier to read.
+    pub fn to_base58(&self) -> String {
+        RecoveryKey::from_bytes(self.secret.to_bytes()).to_base58()
+    }
+
+    /// The curve25519 public key matching this key.
+    ///
+    /// It should be the same as the `public_key` of the backup's auth data.
+    pub fn public_key(&self) -> Base64 {
+        Base64::new(PublicKey::from(&self.secret).as_bytes().to_vec())
+    }
+
+    /// Decrypts the given session data from a backup.
+    pub fn decrypt_session_data(
+        &self,
+        session_data: &EncryptedSessionData,
+    ) -> Result<BackedUpSessionData, BackupDecryptionError> {
+        let ephemeral: [u8; 32] = session_data
+            .ephemeral
+            .as_bytes()
+            .try_into()
+            .map_err(|_| BackupDecryptionError::InvalidEphemeralKey)?;
+        let shared_secret = self.secret.diffie_hellman(&PublicKey::from(ephemeral));
+
+        let mut expanded_keys = [0; 80];
+        Hkdf::<Sha256>::new(Some(&[0; 32]), shared_secret.as_bytes())
+            .expand(b"", &mut expanded_keys)
+            .expect("80 bytes is a valid length for HKDF-SHA-256");
+        let (aes_key, rest) = expanded_keys.split_at(32);
+        let (mac_key, aes_iv) = rest.split_at(32);
+
+        let ciphertext = session_data.ciphertext.as_bytes();
+        let mac = session_data.mac.as_bytes();
+
+        // libolm computes the MAC over an empty string instead of the ciphertext, so both need to
+        // be accepted.
+        let verify_mac = |input: &[u8]| {
+            let mut hmac =
+                Hmac::<Sha256>::new_from_slice(mac_key).expect("HMAC can take a key of any size");
+            hmac.update(input);
+            hmac.verify_truncated_left(mac).is_ok()
+        };
+        if !verify_mac(ciphertext) && !verify_mac(b"") {
+            return Err(BackupDecryptionError::InvalidMac);
+        }
+
+        let plaintext = cbc::Decryptor::<Aes256>::new(aes_key.into(), aes_iv.into())
+            .decrypt_padded_vec_mut::<Pkcs7>(ciphertext)
+            .map_err(|_| BackupDecryptionError::InvalidPadding)?;
+
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+}
+
+impl From<RecoveryKey> for BackupRecoveryKey {
+    fn from(key: RecoveryKey) -> Self {
+        Self::from_bytes(*key.as_bytes())
+    }
+}
+
+impl fmt::Debug for BackupRecoveryKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BackupRecoveryKey").finish_non_exhaustive()
+    }
+}
+
+/// An error encountered when decrypting backed-up session data.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum BackupDecryptionError {
+    /// The ephemeral key is not a valid curve25519 public key.
+    #[error("the ephemeral key is not a valid curve25519 public key")]
+    InvalidEphemeralKey,
+
+    /// The MAC of the session data doesn't match.
+    #[error("the MAC of the session data doesn't match")]
+    InvalidMac,
+
+    /// The decrypted session data has an invalid padding.
+    #[error("the decrypted session data has an invalid padding")]
+    InvalidPadding,
+
+    /// The decrypted session data is not a valid JSON object.
+    #[error("invalid session data: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// The decrypted data of a room key in a `m.megolm_backup.v1.curve25519-aes-sha2` backup.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct BackedUpSessionData {
+    /// The end-to-end message encryption algorithm that the key is for.
+    pub algorithm: EventEncryptionAlgorithm,
+
+    /// Chain of Curve25519 keys through which this session was forwarded, via
+    /// `m.forwarded_room_key` events.
+    pub forwarding_curve25519_key_chain: Vec<String>,
+
+    /// A map from algorithm name to the public part of the key of the device that sent the
+    /// session, claimed by the device that sent the session.
+    pub sender_claimed_keys: BTreeMap<DeviceKeyAlgorithm, String>,
+
+    /// Unpadded base64-encoded device Curve25519 key.
+    pub sender_key: String,
+
+    /// Unpadded base64-encoded session key in session-sharing format.
+    pub session_key: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use aes::{
+        cipher::{block_padding::Pkcs7, BlockEncryptMut, KeyIvInit},
+        Aes256,
+    };
+    use assert_matches2::assert_matches;
+    use hkdf::Hkdf;
+    use hmac::{Hmac, Mac};
+    use ruma_common::{
+        canonical_json::to_canonical_value,
+        device_id,
+        encryption::DeviceKeys,
+        owned_user_id,
+        serde::{base64::Standard, Base64},
+        CanonicalJsonValue, DeviceKeyAlgorithm, DeviceKeyId, EventEncryptionAlgorithm,
+        OwnedDeviceKeyId,
+    };
+    use ruma_events::secret_storage::recovery_key::{RecoveryKey, RecoveryKeyError};
+    use ruma_signatures::{sign_json, Ed25519KeyPair};
+    use serde_json::json;
+    use sha2::Sha256;
+    use x25519_dalek::{PublicKey, StaticSecret};
+
+    use super::{BackupDecryptionError, BackupRecoveryKey, BackupSignatureError};
+    use crate::backup::{EncryptedSessionData, MegolmBackupV1Curve25519AesSha2AuthData};
+
+    fn encrypt(
+        public_key: &Base64,
+        plaintext: &[u8],
+        mac_over_ciphertext: bool,
+    ) -> EncryptedSessionData {
+        let ephemeral = StaticSecret::from([7; 32]);
+        let backup_key: [u8; 32] = public_key.as_bytes().try_into().unwrap();
+        let shared_secret = ephemeral.diffie_hellman(&PublicKey::from(backup_key));
+
+        let mut expanded_keys = [0; 80];
+        Hkdf::<Sha256>::new(Some(&[0; 32]), shared_secret.as_bytes())
+            .expand(b"", &mut expanded_keys)
+            .unwrap();
+        let (aes_key, rest) = expanded_keys.split_at(32);
+        let (mac_key, aes_iv) = rest.split_at(32);
+
+        let ciphertext = cbc::Encryptor::<Aes256>::new(aes_key.into(), aes_iv.into())
+            .encrypt_padded_vec_mut::<Pkcs7>(plaintext);
+
+        let mut hmac = Hmac::<Sha256>::new_from_slice(mac_key).unwrap();
+        if mac_over_ciphertext {
+            hmac.update(&ciphertext);
+        }
+        let mac = hmac.finalize().into_bytes()[..8].to_vec();
+
+        EncryptedSessionData {
+            ephemeral: Base64::new(PublicKey::from(&ephemeral).as_bytes().to_vec()),
+            ciphertext: Base64::new(ciphertext),
+            mac: Base64::new(mac),
+        }
+    }
+
+    #[test]
+    fn recovery_key_roundtrip() {
+        let key = BackupRecoveryKey::from_bytes([1; 32]);
+        let encoded = key.to_base58();
+        assert_eq!(encoded, RecoveryKey::from_bytes([1; 32]).to_base58());
+
+        let decoded = BackupRecoveryKey::from_base58(&encoded).unwrap();
+        assert_eq!(decoded.public_key(), key.public_key());
+
+        assert_matches!(
+            BackupRecoveryKey::from_base58("0OIl"),
+            Err(RecoveryKeyError::NonBase58Character)
+        );
+    }
+
+    #[test]
+    fn decrypt_session_data() {
+        let key = BackupRecoveryKey::from_bytes([2; 32]);
+        let plaintext = json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "forwarding_curve25519_key_chain": [],
+            "sender_claimed_keys": { "ed25519": "aj40p+aw64yPIdsxoog8Jhlu9i0h4PTuUgdkRqEcI6k" },
+            "sender_key": "RF3s+E7RkTQTGF2d8Deol0FkQvgII2aJDf3/Jp5mxVU",
+            "session_key": "AgAAAADxKHa9uFxcXzwYoNueL5Xqi69IkD4sni8Llf",
+        })
+        .to_string();
+
+        for mac_over_ciphertext in [true, false] {
+            let session_data =
+                encrypt(&key.public_key(), plaintext.as_bytes(), mac_over_ciphertext);
+            let decrypted = key.decrypt_session_data(&session_data).unwrap();
+
+            assert_eq!(decrypted.algorithm, EventEncryptionAlgorithm::MegolmV1AesSha2);
+            assert!(decrypted.forwarding_curve25519_key_chain.is_empty());
+            assert_eq!(decrypted.sender_key, "RF3s+E7RkTQTGF2d8Deol0FkQvgII2aJDf3/Jp5mxVU");
+            assert_eq!(
+                decrypted.sender_claimed_keys.get(&DeviceKeyAlgorithm::Ed25519).unwrap(),
+                "aj40p+aw64yPIdsxoog8Jhlu9i0h4PTuUgdkRqEcI6k"
+            );
+        }
+
+        let mut session_data = encrypt(&key.public_key(), plaintext.as_bytes(), true);
+        session_data.mac = Base64::new(vec![0; 8]);
+        assert_matches!(
+            key.decrypt_session_data(&session_data),
+            Err(BackupDecryptionError::InvalidMac)
+        );
+
+        let other_key = BackupRecoveryKey::from_bytes([3; 32]);
+        let session_data = encrypt(&key.public_key(), plaintext.as_bytes(), true);
+        assert_matches!(
+            other_key.decrypt_session_data(&session_data),
+            Err(BackupDecryptionError::InvalidMac)
+        );
+    }
+
+    #[test]
+    fn verify_auth_data_signature() {
+        let user_id = owned_user_id!("@alice:example.org");
+        let device_id = device_id!("ABCDEFGH");
+
+        let document = Ed25519KeyPair::generate().unwrap();
+        let key_pair = Ed25519KeyPair::from_der(&document, device_id.to_string()).unwrap();
+        let key_id = DeviceKeyId::from_parts(DeviceKeyAlgorithm::Ed25519, device_id);
+
+        let public_key = BackupRecoveryKey::from_bytes([4; 32]).public_key();
+        let mut object =
+            match to_canonical_value(MegolmBackupV1Curve25519AesSha2AuthData::new(public_key))
+                .unwrap()
+            {
+                CanonicalJsonValue::Object(object) => object,
+                _ => unreachable!(),
+            };
+        sign_json(user_id.as_str(), &key_pair, &mut object).unwrap();
+
+        let mut auth_data: MegolmBackupV1Curve25519AesSha2AuthData =
+            serde_json::from_value(serde_json::to_value(object).unwrap()).unwrap();
+        // Add a signature from an unknown key, which should be ignored.
+        auth_data.signatures.get_mut(&user_id).unwrap().insert(
+            DeviceKeyId::from_parts(DeviceKeyAlgorithm::Ed25519, device_id!("OTHER")),
+            "c2lnbmF0dXJl".to_owned(),
+        );
+
+        let keys: BTreeMap<OwnedDeviceKeyId, String> = BTreeMap::from([(
+            key_id.clone(),
+            Base64::<Standard>::new(key_pair.public_key().to_vec()).encode(),
+        )]);
+        let device_keys = DeviceKeys::new(
+            user_id.clone(),
+            device_id.to_owned(),
+            vec![EventEncryptionAlgorithm::MegolmV1AesSha2],
+            keys,
+            BTreeMap::new(),
+        );
+        auth_data.verify_device_signature(&device_keys).unwrap();
+
+        let mut tampered = auth_data.clone();
+        tampered.public_key = BackupRecoveryKey::from_bytes([5; 32]).public_key();
+        assert_matches!(
+            tampered.verify_device_signature(&device_keys),
+            Err(BackupSignatureError::Verification(_))
+        );
+
+        let other_device_keys = DeviceKeys::new(
+            user_id,
+            device_id!("IJKLMNOP").to_owned(),
+            vec![],
+            BTreeMap::from([(
+                DeviceKeyId::from_parts(DeviceKeyAlgorithm::Ed25519, device_id!("IJKLMNOP")),
+                Base64::<Standard>::new(key_pair.public_key().to_vec()).encode(),
+            )]),
+            BTreeMap::new(),
+        );
+        assert_matches!(
+            auth_data.verify_device_signature(&other_device_keys),
+            Err(BackupSignatureError::MissingSignature)
+        );
+    }
+}
@@ -18,6 +18,7 @@ pub mod v3 {
         method: GET,
         rate_limited: false,
         authentication: AccessToken,
+        guest_access: true,
         history: {
             1.0 => "/_matrix/client/r0/devices",
             1.1 => "/_matrix/client/v3/devices",
@@ -4,7 +4,7 @@
 //!
 //! [spec]: https://spec.matrix.org/latest/client-server-api/#get_matrixclientversions
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use ruma_common::{
     api::{request, response, MatrixVersion, Metadata},
@@ -60,25 +60,174 @@ impl Response {
     /// or [`.rfind()`][DoubleEndedIterator::rfind] to look for a minimum or maximum version to use
     /// given some constraint.
     pub fn known_versions(&self) -> impl DoubleEndedIterator<Item = MatrixVersion> {
-        self.versions
+        known_versions(&self.versions)
+    }
+
+    /// The versions and features supported by the homeserver, according to this response.
+    pub fn supported_versions(&self) -> SupportedVersions {
+        SupportedVersions::from_parts(&self.versions, &self.unstable_features)
+    }
+}
+
+/// Extracts known Matrix versions from the given list, sorted from oldest to latest.
+fn known_versions(versions: &[String]) -> impl DoubleEndedIterator<Item = MatrixVersion> {
+    versions
+        .iter()
+        // Parse, discard unknown versions
+        .flat_map(|s| s.parse::<MatrixVersion>())
+        // Map to key-value pairs where the key is the major-minor representation
+        // (which can be used as a BTreeMap unlike MatrixVersion itself)
+        .map(|v| (v.into_parts(), v))
+        // Collect to BTreeMap
+        .collect::<BTreeMap<_, _>>()
+        // Return an iterator over just the values (`MatrixVersion`s)
+        .into_values()
+}
+
+/// The versions and unstable features supported by a homeserver.
+///
+/// This can be used to check whether the homeserver supports a [`Feature`], without having to
+/// know which Matrix version or unstable flag it was introduced in.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct SupportedVersions {
+    /// The Matrix versions supported by the homeserver, sorted from oldest to latest.
+    ///
+    /// Matrix versions that Ruma cannot parse, or does not know about, are discarded.
+    pub versions: Vec<MatrixVersion>,
+
+    /// The unstable features that are enabled on the homeserver.
+    pub unstable_features: BTreeSet<String>,
+}
+
+impl SupportedVersions {
+    /// Creates a `SupportedVersions` from the versions and unstable features of a response of
+    /// this endpoint.
+    ///
+    /// Unknown versions and disabled unstable features are discarded.
+    pub fn from_parts(versions: &[String], unstable_features: &BTreeMap<String, bool>) -> Self {
+        let versions = known_versions(versions).collect();
+        let unstable_features = unstable_features
             .iter()
-            // Parse, discard unknown versions
-            .flat_map(|s| s.parse::<MatrixVersion>())
-            // Map to key-value pairs where the key is the major-minor representation
-            // (which can be used as a BTreeMap unlike MatrixVersion itself)
-            .map(|v| (v.into_parts(), v))
-            // Collect to BTreeMap
-            .collect::<BTreeMap<_, _>>()
-            // Return an iterator over just the values (`MatrixVersion`s)
-            .into_values()
+            .filter(|(_, enabled)| **enabled)
+            .map(|(feature, _)| feature.clone())
+            .collect();
+
+        Self { versions, unstable_features }
+    }
+
+    /// Whether the homeserver supports the given Matrix version, or a later one with the same
+    /// major version.
+    pub fn supports_version(&self, version: MatrixVersion) -> bool {
+        self.versions.iter().any(|v| v.is_superset_of(version))
+    }
+
+    /// Whether the given unstable feature is enabled on the homeserver.
+    pub fn supports_unstable_feature(&self, feature: &str) -> bool {
+        self.unstable_features.contains(feature)
+    }
+
+    /// Whether the homeserver supports the given feature, either with a Matrix version that
+    /// includes it or with one of its unstable flags.
+    pub fn supports(&self, feature: Feature) -> bool {
+        feature.stable_version().is_some_and(|version| self.supports_version(version))
+            || feature.unstable_flags().iter().any(|flag| self.supports_unstable_feature(flag))
+    }
+}
+
+/// A feature of the client-server API that a homeserver might support.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum Feature {
+    /// Knocking on rooms ([MSC2403]).
+    ///
+    /// [MSC2403]: https://github.com/matrix-org/matrix-spec-proposals/pull/2403
+    Knocking,
+
+    /// Threads, including the endpoint to list the threads of a room ([MSC3440]).
+    ///
+    /// [MSC3440]: https://github.com/matrix-org/matrix-spec-proposals/pull/3440
+    Threads,
+
+    /// Read receipts for threads ([MSC3771]).
+    ///
+    /// [MSC3771]: https://github.com/matrix-org/matrix-spec-proposals/pull/3771
+    ThreadedReceipts,
+
+    /// Asynchronous media uploads ([MSC2246]).
+    ///
+    /// [MSC2246]: https://github.com/matrix-org/matrix-spec-proposals/pull/2246
+    AsyncMediaUpload,
+
+    /// Generating a token to log in another device ([MSC3882]).
+    ///
+    /// [MSC3882]: https://github.com/matrix-org/matrix-spec-proposals/pull/3882
+    LoginTokenGeneration,
+
+    /// Recursive relations ([MSC3981]).
+    ///
+    /// [MSC3981]: https://github.com/matrix-org/matrix-spec-proposals/pull/3981
+    RecursiveRelations,
+
+    /// Authenticated media endpoints ([MSC3916]).
+    ///
+    /// [MSC3916]: https://github.com/matrix-org/matrix-spec-proposals/pull/3916
+    AuthenticatedMedia,
+
+    /// Getting the summary of a room ([MSC3266]).
+    ///
+    /// [MSC3266]: https://github.com/matrix-org/matrix-spec-proposals/pull/3266
+    RoomSummary,
+
+    /// Querying the rooms shared with another user ([MSC2666]).
+    ///
+    /// [MSC2666]: https://github.com/matrix-org/matrix-spec-proposals/pull/2666
+    MutualRooms,
+
+    /// Delayed events ([MSC4140]).
+    ///
+    /// [MSC4140]: https://github.com/matrix-org/matrix-spec-proposals/pull/4140
+    DelayedEvents,
+}
+
+impl Feature {
+    /// The Matrix version that includes this feature, if it was stabilized.
+    pub fn stable_version(self) -> Option<MatrixVersion> {
+        match self {
+            Self::Knocking => Some(MatrixVersion::V1_1),
+            Self::Threads | Self::ThreadedReceipts => Some(MatrixVersion::V1_4),
+            Self::AsyncMediaUpload | Self::LoginTokenGeneration => Some(MatrixVersion::V1_7),
+            Self::RecursiveRelations => Some(MatrixVersion::V1_10),
+            Self::AuthenticatedMedia => Some(MatrixVersion::V1_11),
+            Self::RoomSummary | Self::MutualRooms | Self::DelayedEvents => None,
+        }
+    }
+
+    /// The flags in `unstable_features` that advertise support for this feature before it was
+    /// stabilized, or in addition to the Matrix versions.
+    pub fn unstable_flags(self) -> &'static [&'static str] {
+        match self {
+            Self::Knocking => &["xyz.amorgan.knock"],
+            Self::Threads => &["org.matrix.msc3440.stable"],
+            Self::ThreadedReceipts => &["org.matrix.msc3771"],
+            Self::AsyncMediaUpload => &["fi.mau.msc2246.stable"],
+            Self::LoginTokenGeneration => &["org.matrix.msc3882"],
+            Self::RecursiveRelations => &["org.matrix.msc3981"],
+            Self::AuthenticatedMedia => &["org.matrix.msc3916.stable"],
+            Self::RoomSummary => &["org.matrix.msc3266"],
+            Self::MutualRooms => &["uk.half-shot.msc2666.query_mutual_rooms"],
+            Self::DelayedEvents => &["org.matrix.msc4140"],
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use ruma_common::api::MatrixVersion;
 
-    use super::Response;
+    use super::{Feature, Response};
 
     #[test]
     fn known_versions() {
@@ -133,4 +282,39 @@ mod tests {
             vec![MatrixVersion::V1_0, MatrixVersion::V1_1, MatrixVersion::V1_2],
         );
     }
+
+    #[test]
+    fn supported_features() {
+        let mut response = Response::new(vec![
+            "r0.6.1".to_owned(),
+            "v1.1".to_owned(),
+            "v1.4".to_owned(),
+            "v42.0".to_owned(),
+        ]);
+        response.unstable_features = BTreeMap::from([
+            ("org.matrix.msc3916.stable".to_owned(), true),
+            ("org.matrix.msc4140".to_owned(), false),
+            ("org.matrix.msc3266".to_owned(), true),
+        ]);
+
+        let supported = response.supported_versions();
+        assert_eq!(
+            supported.versions,
+            vec![MatrixVersion::V1_0, MatrixVersion::V1_1, MatrixVersion::V1_4]
+        );
+        assert!(supported.supports_version(MatrixVersion::V1_2));
+        assert!(!supported.supports_version(MatrixVersion::V1_5));
+        assert!(!supported.supports_unstable_feature("org.matrix.msc4140"));
+
+        // Supported with a stable version.
+        assert!(supported.supports(Feature::Knocking));
+        assert!(supported.supports(Feature::Threads));
+        assert!(!supported.supports(Feature::AsyncMediaUpload));
+
+        // Supported with an unstable flag.
+        assert!(supported.supports(Feature::AuthenticatedMedia));
+        assert!(supported.supports(Feature::RoomSummary));
+        assert!(!supported.supports(Feature::DelayedEvents));
+        assert!(!supported.supports(Feature::MutualRooms));
+    }
 }
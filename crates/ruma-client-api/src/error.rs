@@ -350,6 +350,56 @@ impl Error {
         Self { status_code, body }
     }
 
+    /// Constructs a new `Error` with a standard body, using the given status code, error kind
+    /// and message.
+    pub fn standard(
+        status_code: http::StatusCode,
+        kind: ErrorKind,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::new(status_code, ErrorBody::Standard { kind, message: message.into() })
+    }
+
+    /// Constructs an `M_FORBIDDEN` error with a `403 Forbidden` status code.
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::standard(http::StatusCode::FORBIDDEN, ErrorKind::forbidden(), message)
+    }
+
+    /// Constructs an `M_NOT_FOUND` error with a `404 Not Found` status code.
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::standard(http::StatusCode::NOT_FOUND, ErrorKind::NotFound, message)
+    }
+
+    /// Constructs an `M_LIMIT_EXCEEDED` error with a `429 Too Many Requests` status code.
+    ///
+    /// If `retry_after` is set, it is also sent in the `Retry-After` header.
+    pub fn limit_exceeded(retry_after: Option<RetryAfter>, message: impl Into<String>) -> Self {
+        Self::standard(
+            http::StatusCode::TOO_MANY_REQUESTS,
+            ErrorKind::LimitExceeded { retry_after },
+            message,
+        )
+    }
+
+    /// Constructs an `M_MISSING_TOKEN` error with a `401 Unauthorized` status code.
+    pub fn missing_token(message: impl Into<String>) -> Self {
+        Self::standard(http::StatusCode::UNAUTHORIZED, ErrorKind::MissingToken, message)
+    }
+
+    /// Constructs an `M_UNKNOWN_TOKEN` error with a `401 Unauthorized` status code.
+    pub fn unknown_token(soft_logout: bool, message: impl Into<String>) -> Self {
+        Self::standard(
+            http::StatusCode::UNAUTHORIZED,
+            ErrorKind::UnknownToken { soft_logout },
+            message,
+        )
+    }
+
+    /// Constructs an `M_BAD_JSON` error with a `400 Bad Request` status code.
+    pub fn bad_json(message: impl Into<String>) -> Self {
+        Self::standard(http::StatusCode::BAD_REQUEST, ErrorKind::BadJson, message)
+    }
+
     /// If `self` is a server error in the `errcode` + `error` format expected
     /// for client-server API endpoints, returns the error kind (`errcode`).
     pub fn error_kind(&self) -> Option<&ErrorKind> {
@@ -927,4 +977,36 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn serialize_error_helpers() {
+        let response = Error::forbidden("You are not invited to this room.")
+            .try_into_http_response::<Vec<u8>>()
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+        let json_body: JsonValue = from_json_slice(response.body()).unwrap();
+        assert_eq!(
+            json_body,
+            json!({
+                "errcode": "M_FORBIDDEN",
+                "error": "You are not invited to this room.",
+            })
+        );
+
+        let response = Error::limit_exceeded(
+            Some(RetryAfter::Delay(Duration::from_secs(3))),
+            "Too many requests",
+        )
+        .try_into_http_response::<Vec<u8>>()
+        .unwrap();
+        assert_eq!(response.status(), http::StatusCode::TOO_MANY_REQUESTS);
+        let retry_after_header = response.headers().get(http::header::RETRY_AFTER).unwrap();
+        assert_eq!(retry_after_header.to_str().unwrap(), "3");
+        let json_body: JsonValue = from_json_slice(response.body()).unwrap();
+        assert_eq!(json_body["retry_after_ms"], 3000);
+
+        let error = Error::not_found("Event not found.");
+        assert_eq!(error.status_code, http::StatusCode::NOT_FOUND);
+        assert_eq!(error.error_kind(), Some(&ErrorKind::NotFound));
+    }
 }
@@ -6,9 +6,17 @@ pub mod get_filter;
 mod lazy_load;
 mod url;
 
+#[cfg(feature = "unstable-msc3874")]
+use std::collections::BTreeMap;
+
 use js_int::UInt;
-use ruma_common::{serde::StringEnum, OwnedRoomId, OwnedUserId};
-use serde::{Deserialize, Serialize};
+use ruma_common::{
+    serde::{Raw, StringEnum},
+    OwnedRoomId, OwnedUserId, RoomId, UserId,
+};
+#[cfg(feature = "unstable-msc3874")]
+use ruma_events::relation::RelationType;
+use serde::{de::IgnoredAny, Deserialize, Serialize};
 
 pub use self::{lazy_load::LazyLoadOptions, url::UrlFilter};
 use crate::PrivOwnedStr;
@@ -101,6 +109,35 @@ pub struct RoomEventFilter {
     /// [`sync_events`]: crate::sync::sync_events
     #[serde(default, skip_serializing_if = "ruma_common::serde::is_default")]
     pub unread_thread_notifications: bool,
+
+    /// A list of relation types to include.
+    ///
+    /// If this list is absent then all events are included. Otherwise, only events that relate to
+    /// another event with one of these relation types are included.
+    ///
+    /// This uses the unstable prefix defined in [MSC3874].
+    ///
+    /// [MSC3874]: https://github.com/matrix-org/matrix-spec-proposals/pull/3874
+    #[cfg(feature = "unstable-msc3874")]
+    #[serde(rename = "org.matrix.msc3874.rel_types", skip_serializing_if = "Option::is_none")]
+    pub rel_types: Option<Vec<RelationType>>,
+
+    /// A list of relation types to exclude.
+    ///
+    /// If this list is absent then no events are excluded. Events that relate to another event
+    /// with one of these relation types are excluded, even if the relation type is listed in
+    /// `rel_types`. This can be used to exclude threaded events from the main timeline.
+    ///
+    /// This uses the unstable prefix defined in [MSC3874].
+    ///
+    /// [MSC3874]: https://github.com/matrix-org/matrix-spec-proposals/pull/3874
+    #[cfg(feature = "unstable-msc3874")]
+    #[serde(
+        rename = "org.matrix.msc3874.not_rel_types",
+        default,
+        skip_serializing_if = "<[_]>::is_empty"
+    )]
+    pub not_rel_types: Vec<RelationType>,
 }
 
 impl RoomEventFilter {
@@ -140,6 +177,114 @@ impl RoomEventFilter {
             && self.url_filter.is_none()
             && self.lazy_load_options.is_disabled()
             && !self.unread_thread_notifications
+            && self.rel_types_is_empty()
+    }
+
+    #[cfg(feature = "unstable-msc3874")]
+    fn rel_types_is_empty(&self) -> bool {
+        self.rel_types.is_none() && self.not_rel_types.is_empty()
+    }
+
+    #[cfg(not(feature = "unstable-msc3874"))]
+    fn rel_types_is_empty(&self) -> bool {
+        true
+    }
+
+    /// Whether the given event type is allowed by the `types` and `not_types` fields.
+    pub fn matches_event_type(&self, event_type: &str) -> bool {
+        matches_event_type(self.types.as_deref(), &self.not_types, event_type)
+    }
+
+    /// Whether the given sender is allowed by the `senders` and `not_senders` fields.
+    pub fn matches_sender(&self, sender: &UserId) -> bool {
+        matches_list(self.senders.as_deref(), &self.not_senders, sender)
+    }
+
+    /// Whether the given room is allowed by the `rooms` and `not_rooms` fields.
+    pub fn matches_room(&self, room_id: &RoomId) -> bool {
+        matches_list(self.rooms.as_deref(), &self.not_rooms, room_id)
+    }
+
+    /// Whether an event with or without a `url` key in its content is allowed by the
+    /// `url_filter` field.
+    pub fn matches_url(&self, has_url: bool) -> bool {
+        match self.url_filter {
+            None => true,
+            Some(UrlFilter::EventsWithUrl) => has_url,
+            Some(UrlFilter::EventsWithoutUrl) => !has_url,
+        }
+    }
+
+    /// Whether an event with the given relation type is allowed by the `rel_types` and
+    /// `not_rel_types` fields.
+    ///
+    /// `rel_type` should be `None` if the event doesn't relate to another event. Such events are
+    /// excluded if `rel_types` is set.
+    #[cfg(feature = "unstable-msc3874")]
+    pub fn matches_rel_type(&self, rel_type: Option<&RelationType>) -> bool {
+        match rel_type {
+            Some(rel_type) => {
+                matches_list(self.rel_types.as_deref(), &self.not_rel_types, rel_type.as_str())
+            }
+            None => self.rel_types.is_none(),
+        }
+    }
+
+    /// Whether the given event is allowed by this filter.
+    ///
+    /// This checks the `type`, `sender`, `room_id` and `content.url` fields of the event against
+    /// the corresponding fields of this filter. If the event doesn't have a `room_id` field, like
+    /// events in the sync response, the room should be checked separately with
+    /// [`matches_room()`](Self::matches_room).
+    ///
+    /// Events that can't be deserialized as a Matrix event never match.
+    ///
+    /// The `limit` and `lazy_load_options` fields apply to a whole set of events, so they are not
+    /// taken into account here and need to be handled by the caller, after filtering the events.
+    pub fn matches<T>(&self, event: &Raw<T>) -> bool {
+        let Ok(fields) = event.deserialize_as::<FilterEventFields>() else {
+            return false;
+        };
+
+        self.matches_event_type(&fields.event_type)
+            && fields.sender.map_or(true, |sender| self.matches_sender(&sender))
+            && fields.room_id.map_or(true, |room_id| self.matches_room(&room_id))
+            && self.matches_url(fields.content.url.is_some())
+            && self.matches_event_relation(&fields.content)
+    }
+
+    #[cfg(feature = "unstable-msc3874")]
+    fn matches_event_relation(&self, content: &FilterEventContentFields) -> bool {
+        let rel_type = content.relates_to.as_ref().and_then(|r| r.rel_type.as_ref());
+        self.matches_rel_type(rel_type)
+    }
+
+    #[cfg(not(feature = "unstable-msc3874"))]
+    fn matches_event_relation(&self, _content: &FilterEventContentFields) -> bool {
+        true
+    }
+
+    /// Splits this filter into the part that is supported by the server and the part that must
+    /// be applied by the client.
+    ///
+    /// `unstable_features` is the list of unstable features supported by the server, as returned
+    /// by the [`get_supported_versions`] endpoint. Criteria that rely on an unstable feature that
+    /// is not supported are moved to the client part.
+    ///
+    /// Note that `limit` is kept in the server part, so fewer events than the limit might remain
+    /// after applying the client part.
+    ///
+    /// [`get_supported_versions`]: crate::discovery::get_supported_versions
+    #[cfg(feature = "unstable-msc3874")]
+    pub fn split(mut self, unstable_features: &BTreeMap<String, bool>) -> SplitFilter<Self> {
+        let mut client = Self::empty();
+
+        if !unstable_features.get("org.matrix.msc3874").copied().unwrap_or(false) {
+            client.rel_types = self.rel_types.take();
+            client.not_rel_types = std::mem::take(&mut self.not_rel_types);
+        }
+
+        SplitFilter { server: self, client }
     }
 }
 
@@ -218,6 +363,14 @@ impl RoomFilter {
             && self.not_rooms.is_empty()
             && self.rooms.is_none()
     }
+
+    /// Whether the given room is allowed by the `rooms` and `not_rooms` fields.
+    ///
+    /// This should be checked before applying the `ephemeral`, `state`, `timeline` and
+    /// `account_data` filters.
+    pub fn matches_room(&self, room_id: &RoomId) -> bool {
+        matches_list(self.rooms.as_deref(), &self.not_rooms, room_id)
+    }
 }
 
 /// Filter for non-room data.
@@ -278,6 +431,34 @@ impl Filter {
             && self.types.is_none()
             && self.not_senders.is_empty()
     }
+
+    /// Whether the given event type is allowed by the `types` and `not_types` fields.
+    pub fn matches_event_type(&self, event_type: &str) -> bool {
+        matches_event_type(self.types.as_deref(), &self.not_types, event_type)
+    }
+
+    /// Whether the given sender is allowed by the `senders` and `not_senders` fields.
+    pub fn matches_sender(&self, sender: &UserId) -> bool {
+        matches_list(self.senders.as_deref(), &self.not_senders, sender)
+    }
+
+    /// Whether the given event is allowed by this filter.
+    ///
+    /// This checks the `type` and `sender` fields of the event against the corresponding fields
+    /// of this filter.
+    ///
+    /// Events that can't be deserialized as a Matrix event never match.
+    ///
+    /// The `limit` field applies to a whole set of events, so it is not taken into account here
+    /// and needs to be handled by the caller, after filtering the events.
+    pub fn matches<T>(&self, event: &Raw<T>) -> bool {
+        let Ok(fields) = event.deserialize_as::<FilterEventFields>() else {
+            return false;
+        };
+
+        self.matches_event_type(&fields.event_type)
+            && fields.sender.map_or(true, |sender| self.matches_sender(&sender))
+    }
 }
 
 /// A filter definition
@@ -340,6 +521,57 @@ impl FilterDefinition {
         Self { room: RoomFilter::with_lazy_loading(), ..Default::default() }
     }
 
+    /// Enable [room member lazy-loading] for the state of the rooms in this filter.
+    ///
+    /// [room member lazy-loading]: https://spec.matrix.org/latest/client-server-api/#lazy-loading-room-members
+    pub fn lazy_load_members(mut self, include_redundant_members: bool) -> Self {
+        self.room.state.lazy_load_options = LazyLoadOptions::Enabled { include_redundant_members };
+        self
+    }
+
+    /// Only include the rooms with the given IDs in this filter.
+    ///
+    /// An empty list means that no rooms are included.
+    pub fn only_rooms(mut self, rooms: Vec<OwnedRoomId>) -> Self {
+        self.room.rooms = Some(rooms);
+        self
+    }
+
+    /// Set the maximum number of events to return in the timeline of each room.
+    pub fn timeline_limit(mut self, limit: UInt) -> Self {
+        self.room.timeline.limit = Some(limit);
+        self
+    }
+
+    /// Don't include any presence updates in this filter.
+    pub fn ignore_presence(mut self) -> Self {
+        self.presence = Filter::ignore_all();
+        self
+    }
+
+    /// Splits this filter definition into the part that is supported by the server and the part
+    /// that must be applied by the client.
+    ///
+    /// The filters for room events are split with [`RoomEventFilter::split()`]. The other filters
+    /// are always supported by the server, so they are only present in the server part.
+    #[cfg(feature = "unstable-msc3874")]
+    pub fn split(mut self, unstable_features: &BTreeMap<String, bool>) -> SplitFilter<Self> {
+        let mut client = Self::empty();
+
+        for (server_filter, client_filter) in [
+            (&mut self.room.account_data, &mut client.room.account_data),
+            (&mut self.room.timeline, &mut client.room.timeline),
+            (&mut self.room.ephemeral, &mut client.room.ephemeral),
+            (&mut self.room.state, &mut client.room.state),
+        ] {
+            let split = std::mem::take(server_filter).split(unstable_features);
+            *server_filter = split.server;
+            *client_filter = split.client;
+        }
+
+        SplitFilter { server: self, client }
+    }
+
     /// Returns `true` if all fields are empty.
     pub fn is_empty(&self) -> bool {
         self.event_fields.is_none()
@@ -350,6 +582,23 @@ impl FilterDefinition {
     }
 }
 
+/// A filter split into the part that is supported by the server and the part that must be applied
+/// by the client.
+///
+/// To construct this, use [`FilterDefinition::split()`] or [`RoomEventFilter::split()`].
+#[cfg(feature = "unstable-msc3874")]
+#[derive(Clone, Debug, Default)]
+#[allow(clippy::exhaustive_structs)]
+pub struct SplitFilter<T> {
+    /// The part of the filter to send to the server.
+    pub server: T,
+
+    /// The part of the filter to apply to the events returned by the server.
+    ///
+    /// It is empty if the server supports the whole filter.
+    pub client: T,
+}
+
 macro_rules! can_be_empty {
     ($ty:ident) => {
         impl ruma_common::serde::CanBeEmpty for $ty {
@@ -365,12 +614,100 @@ can_be_empty!(FilterDefinition);
 can_be_empty!(RoomEventFilter);
 can_be_empty!(RoomFilter);
 
+/// The fields of an event that are checked by filters.
+#[derive(Deserialize)]
+struct FilterEventFields {
+    #[serde(rename = "type")]
+    event_type: String,
+    sender: Option<OwnedUserId>,
+    room_id: Option<OwnedRoomId>,
+    #[serde(default)]
+    content: FilterEventContentFields,
+}
+
+/// The fields of an event's content that are checked by filters.
+#[derive(Default, Deserialize)]
+struct FilterEventContentFields {
+    url: Option<IgnoredAny>,
+    #[cfg(feature = "unstable-msc3874")]
+    #[serde(rename = "m.relates_to", default, deserialize_with = "none_on_invalid")]
+    relates_to: Option<FilterEventRelatesTo>,
+}
+
+/// The fields of an event's relation that are checked by filters.
+#[cfg(feature = "unstable-msc3874")]
+#[derive(Deserialize)]
+struct FilterEventRelatesTo {
+    rel_type: Option<RelationType>,
+}
+
+/// Deserializes an optional field, falling back to `None` if its value is invalid.
+///
+/// This allows to check the other fields of an event with a malformed relation.
+#[cfg(feature = "unstable-msc3874")]
+fn none_on_invalid<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let Some(json) = Option::<Box<serde_json::value::RawValue>>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    Ok(serde_json::from_str(json.get()).ok())
+}
+
+/// Whether the given value is in the `included` list, if any, and not in the `excluded` list.
+fn matches_list<T, U>(included: Option<&[T]>, excluded: &[T], value: &U) -> bool
+where
+    T: AsRef<U>,
+    U: PartialEq + ?Sized,
+{
+    let is_in = |list: &[T]| list.iter().any(|item| item.as_ref() == value);
+    !is_in(excluded) && included.map_or(true, is_in)
+}
+
+/// Whether the given event type is in the `types` list, if any, and not in the `not_types` list.
+///
+/// The entries of the lists can contain `*` wildcards.
+fn matches_event_type(types: Option<&[String]>, not_types: &[String], event_type: &str) -> bool {
+    let is_in = |list: &[String]| list.iter().any(|pattern| glob_matches(pattern, event_type));
+    !is_in(not_types) && types.map_or(true, is_in)
+}
+
+/// Whether the given value matches the pattern, where `*` matches any sequence of characters.
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+
+    // There is always at least one part.
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+
+    let Some(last) = parts.next_back() else {
+        // There is no wildcard.
+        return rest.is_empty();
+    };
+
+    for part in parts {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
 #[cfg(test)]
 mod tests {
+    use js_int::uint;
+    use ruma_common::{owned_room_id, owned_user_id, room_id, serde::Raw, user_id};
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::{
-        Filter, FilterDefinition, LazyLoadOptions, RoomEventFilter, RoomFilter, UrlFilter,
+        glob_matches, Filter, FilterDefinition, LazyLoadOptions, RoomEventFilter, RoomFilter,
+        UrlFilter,
     };
 
     #[test]
@@ -383,6 +720,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn filter_definition_chained_options() -> serde_json::Result<()> {
+        let filter = FilterDefinition::empty()
+            .lazy_load_members(false)
+            .only_rooms(vec![owned_room_id!("!room:example.org")])
+            .timeline_limit(uint!(10))
+            .ignore_presence();
+
+        assert_eq!(
+            to_json_value(filter)?,
+            json!({
+                "presence": {
+                    "types": [],
+                },
+                "room": {
+                    "rooms": ["!room:example.org"],
+                    "state": {
+                        "lazy_load_members": true,
+                    },
+                    "timeline": {
+                        "limit": 10,
+                    },
+                },
+            })
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn filter_definition_roundtrip() -> serde_json::Result<()> {
         let filter = FilterDefinition::default();
@@ -434,4 +800,199 @@ mod tests {
             LazyLoadOptions::Enabled { include_redundant_members: false }
         );
     }
+
+    #[test]
+    fn glob() {
+        assert!(glob_matches("m.room.message", "m.room.message"));
+        assert!(!glob_matches("m.room.message", "m.room.messages"));
+        assert!(!glob_matches("m.room.message", "m.room.messag"));
+        assert!(glob_matches("*", ""));
+        assert!(glob_matches("*", "m.room.message"));
+        assert!(glob_matches("m.room.*", "m.room.message"));
+        assert!(!glob_matches("m.room.*", "m.call.invite"));
+        assert!(glob_matches("*.message", "m.room.message"));
+        assert!(glob_matches("m.*.m*e", "m.room.message"));
+        assert!(!glob_matches("m.*.m*e", "m.room.messages"));
+        assert!(!glob_matches("a*a", "a"));
+        assert!(glob_matches("a*a", "aa"));
+    }
+
+    #[test]
+    fn room_event_filter_matches() {
+        let event = Raw::new(&json!({
+            "type": "m.room.message",
+            "sender": "@alice:example.org",
+            "room_id": "!room:example.org",
+            "content": {
+                "msgtype": "m.image",
+                "body": "image.png",
+                "url": "mxc://example.org/image",
+            },
+        }))
+        .unwrap();
+
+        assert!(RoomEventFilter::empty().matches(&event));
+        assert!(!RoomEventFilter::ignore_all().matches(&event));
+
+        let mut filter = RoomEventFilter::empty();
+        filter.types = Some(vec!["m.room.*".to_owned()]);
+        filter.senders = Some(vec![owned_user_id!("@alice:example.org")]);
+        filter.url_filter = Some(UrlFilter::EventsWithUrl);
+        assert!(filter.matches(&event));
+
+        filter.not_types = vec!["m.room.message".to_owned()];
+        assert!(!filter.matches(&event));
+        filter.not_types = vec![];
+
+        filter.not_rooms = vec![owned_room_id!("!room:example.org")];
+        assert!(!filter.matches(&event));
+        assert!(!filter.matches_room(room_id!("!room:example.org")));
+        filter.not_rooms = vec![];
+
+        filter.url_filter = Some(UrlFilter::EventsWithoutUrl);
+        assert!(!filter.matches(&event));
+        filter.url_filter = None;
+
+        filter.not_senders = vec![owned_user_id!("@alice:example.org")];
+        assert!(!filter.matches(&event));
+        assert!(!filter.matches_sender(user_id!("@alice:example.org")));
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-msc3874")]
+    fn room_event_filter_rel_types() -> serde_json::Result<()> {
+        use ruma_events::relation::RelationType;
+
+        let thread_event = Raw::new(&json!({
+            "type": "m.room.message",
+            "sender": "@alice:example.org",
+            "content": {
+                "msgtype": "m.text",
+                "body": "In a thread",
+                "m.relates_to": {
+                    "rel_type": "m.thread",
+                    "event_id": "$root",
+                },
+            },
+        }))
+        .unwrap();
+        let main_event = Raw::new(&json!({
+            "type": "m.room.message",
+            "sender": "@alice:example.org",
+            "content": {
+                "msgtype": "m.text",
+                "body": "In the main timeline",
+            },
+        }))
+        .unwrap();
+
+        let mut filter = RoomEventFilter::empty();
+        filter.not_rel_types = vec![RelationType::Thread];
+        assert!(!filter.is_empty());
+        assert_eq!(
+            to_json_value(&filter)?,
+            json!({ "org.matrix.msc3874.not_rel_types": ["m.thread"] })
+        );
+        assert!(!filter.matches(&thread_event));
+        assert!(filter.matches(&main_event));
+
+        let mut filter = RoomEventFilter::empty();
+        filter.rel_types = Some(vec![RelationType::Thread]);
+        assert!(filter.matches(&thread_event));
+        assert!(!filter.matches(&main_event));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-msc3874")]
+    fn room_event_filter_invalid_relation() {
+        use ruma_events::relation::RelationType;
+
+        let invalid_relates_to = Raw::new(&json!({
+            "type": "m.room.message",
+            "sender": "@alice:example.org",
+            "content": {
+                "msgtype": "m.text",
+                "body": "Invalid relation",
+                "m.relates_to": "$root",
+            },
+        }))
+        .unwrap();
+        let invalid_rel_type = Raw::new(&json!({
+            "type": "m.room.message",
+            "sender": "@alice:example.org",
+            "content": {
+                "msgtype": "m.text",
+                "body": "Invalid relation type",
+                "m.relates_to": {
+                    "rel_type": 1,
+                    "event_id": "$root",
+                },
+            },
+        }))
+        .unwrap();
+
+        // The invalid relation is ignored.
+        let filter = RoomEventFilter::empty();
+        assert!(filter.matches(&invalid_relates_to));
+        assert!(filter.matches(&invalid_rel_type));
+
+        let mut filter = RoomEventFilter::empty();
+        filter.not_rel_types = vec![RelationType::Thread];
+        assert!(filter.matches(&invalid_relates_to));
+        assert!(filter.matches(&invalid_rel_type));
+
+        let mut filter = RoomEventFilter::empty();
+        filter.rel_types = Some(vec![RelationType::Thread]);
+        assert!(!filter.matches(&invalid_relates_to));
+        assert!(!filter.matches(&invalid_rel_type));
+    }
+
+    #[test]
+    #[cfg(feature = "unstable-msc3874")]
+    fn filter_definition_split() {
+        use std::collections::BTreeMap;
+
+        use ruma_events::relation::RelationType;
+
+        let mut filter = FilterDefinition::empty().timeline_limit(uint!(10));
+        filter.room.timeline.not_rel_types = vec![RelationType::Thread];
+
+        let split = filter.clone().split(&BTreeMap::new());
+        assert_eq!(split.server.room.timeline.limit, Some(uint!(10)));
+        assert!(split.server.room.timeline.not_rel_types.is_empty());
+        assert_eq!(split.client.room.timeline.not_rel_types, [RelationType::Thread]);
+        assert!(split.client.room.state.is_empty());
+
+        let unstable_features = BTreeMap::from([("org.matrix.msc3874".to_owned(), true)]);
+        let split = filter.split(&unstable_features);
+        assert_eq!(split.server.room.timeline.not_rel_types, [RelationType::Thread]);
+        assert!(split.client.is_empty());
+    }
+
+    #[test]
+    fn filter_matches() {
+        let event = Raw::new(&json!({
+            "type": "m.presence",
+            "sender": "@alice:example.org",
+            "content": {
+                "presence": "online",
+            },
+        }))
+        .unwrap();
+
+        assert!(Filter::empty().matches(&event));
+        assert!(!Filter::ignore_all().matches(&event));
+
+        let mut filter = Filter::empty();
+        filter.types = Some(vec!["m.*".to_owned()]);
+        assert!(filter.matches(&event));
+
+        filter.senders = Some(vec![owned_user_id!("@bob:example.org")]);
+        assert!(!filter.matches(&event));
+
+        let invalid_event = Raw::new(&json!({ "content": {} })).unwrap();
+        assert!(!Filter::empty().matches(&invalid_event));
+    }
 }
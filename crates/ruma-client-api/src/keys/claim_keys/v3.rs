@@ -17,6 +17,7 @@ const METADATA: Metadata = metadata! {
     method: POST,
     rate_limited: false,
     authentication: AccessToken,
+    guest_access: true,
     history: {
         1.0 => "/_matrix/client/r0/keys/claim",
         1.1 => "/_matrix/client/v3/keys/claim",
@@ -12,10 +12,13 @@ pub mod v3 {
         metadata, OwnedUserId,
     };
 
+    use crate::SyncToken;
+
     const METADATA: Metadata = metadata! {
         method: GET,
         rate_limited: false,
         authentication: AccessToken,
+        guest_access: true,
         history: {
             1.0 => "/_matrix/client/r0/keys/changes",
             1.1 => "/_matrix/client/v3/keys/changes",
@@ -29,14 +32,14 @@ pub mod v3 {
         ///
         /// Should be the next_batch field from a response to an earlier call to /sync.
         #[ruma_api(query)]
-        pub from: String,
+        pub from: SyncToken,
 
         /// The desired end point of the list.
         ///
         /// Should be the next_batch field from a recent call to /sync - typically the most recent
         /// such call.
         #[ruma_api(query)]
-        pub to: String,
+        pub to: SyncToken,
     }
 
     /// Response type for the `get_key_changes` endpoint.
@@ -52,7 +55,7 @@ pub mod v3 {
 
     impl Request {
         /// Creates a new `Request` with the given start and end points.
-        pub fn new(from: String, to: String) -> Self {
+        pub fn new(from: SyncToken, to: SyncToken) -> Self {
             Self { from, to }
         }
     }
@@ -22,6 +22,7 @@ pub mod v3 {
         method: POST,
         rate_limited: false,
         authentication: AccessToken,
+        guest_access: true,
         history: {
             1.0 => "/_matrix/client/r0/keys/query",
             1.1 => "/_matrix/client/v3/keys/query",
@@ -51,6 +51,7 @@ pub mod tag;
 pub mod thirdparty;
 pub mod threads;
 pub mod to_device;
+mod token;
 pub mod typing;
 pub mod uiaa;
 pub mod user_directory;
@@ -58,7 +59,10 @@ pub mod voip;
 
 use std::fmt;
 
-pub use error::Error;
+pub use self::{
+    error::Error,
+    token::{PaginationToken, SyncToken},
+};
 
 // Wrapper around `Box<str>` that cannot be used in a meaningful way outside of
 // this crate. Used for string enums because their `_Custom` variant can't be
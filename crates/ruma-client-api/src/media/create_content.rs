@@ -83,6 +83,14 @@ pub mod v3 {
                 generate_blurhash: false,
             }
         }
+
+        /// The size of the body of this request, in bytes.
+        ///
+        /// This is the size of the file, which can be compared to the maximum upload size from the
+        /// `get_media_config` endpoint.
+        pub fn body_size(&self) -> usize {
+            self.file.len()
+        }
     }
 
     impl Response {
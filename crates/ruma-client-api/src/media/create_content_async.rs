@@ -64,5 +64,13 @@ pub mod v3 {
             let (server_name, media_id) = url.parts()?;
             Ok(Self::new(media_id.to_owned(), server_name.to_owned(), file))
         }
+
+        /// The size of the body of this request, in bytes.
+        ///
+        /// This is the size of the file, which can be compared to the maximum upload size from the
+        /// `get_media_config` endpoint.
+        pub fn body_size(&self) -> usize {
+            self.file.len()
+        }
     }
 }
@@ -54,5 +54,10 @@ pub mod v3 {
         pub fn new(upload_size: UInt) -> Self {
             Self { upload_size }
         }
+
+        /// Whether a file of the given size, in bytes, can be uploaded to the homeserver.
+        pub fn allows_upload_size(&self, size: usize) -> bool {
+            u64::try_from(size).is_ok_and(|size| size <= u64::from(self.upload_size))
+        }
     }
 }
@@ -21,6 +21,7 @@ pub mod v3 {
         method: GET,
         rate_limited: false,
         authentication: AccessToken,
+        guest_access: true,
         history: {
             1.0 => "/_matrix/client/r0/rooms/:room_id/members",
             1.1 => "/_matrix/client/v3/rooms/:room_id/members",
@@ -7,11 +7,15 @@ pub mod v3 {
     //!
     //! [spec]: https://spec.matrix.org/latest/client-server-api/#get_matrixclientv3joined_rooms
 
+    use std::collections::BTreeSet;
+
     use ruma_common::{
         api::{request, response, Metadata},
-        metadata, OwnedRoomId,
+        metadata, OwnedRoomId, RoomId,
     };
 
+    use crate::sync::sync_events::v3::Rooms;
+
     const METADATA: Metadata = metadata! {
         method: GET,
         rate_limited: false,
@@ -48,4 +52,107 @@ pub mod v3 {
             Self { joined_rooms }
         }
     }
+
+    /// A snapshot of the rooms that the user is joined to.
+    ///
+    /// It can be created from the response of the `joined_rooms` endpoint and kept up to date with
+    /// the `rooms` of the responses of the [`sync_events`] endpoint.
+    ///
+    /// After a gap in the sync responses, for example when the client was offline for a long time
+    /// and the server returned a limited timeline, a new snapshot can be fetched and compared with
+    /// the previous one to find the rooms that were joined or left in the meantime.
+    ///
+    /// [`sync_events`]: crate::sync::sync_events
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct MembershipSnapshot {
+        joined: BTreeSet<OwnedRoomId>,
+    }
+
+    impl MembershipSnapshot {
+        /// Creates a new `MembershipSnapshot` with the given joined rooms.
+        pub fn new(joined_rooms: impl IntoIterator<Item = OwnedRoomId>) -> Self {
+            Self { joined: joined_rooms.into_iter().collect() }
+        }
+
+        /// Update this snapshot with the `rooms` of a sync response.
+        ///
+        /// Rooms in the `join` section are added, and rooms in the `leave`, `invite` and `knock`
+        /// sections are removed.
+        pub fn apply_sync_rooms(&mut self, rooms: &Rooms) {
+            self.joined.extend(rooms.join.keys().cloned());
+
+            for room_id in rooms.leave.keys().chain(rooms.invite.keys()).chain(rooms.knock.keys()) {
+                self.joined.remove(room_id);
+            }
+        }
+
+        /// Whether the user is joined to the room with the given ID.
+        pub fn is_joined(&self, room_id: &RoomId) -> bool {
+            self.joined.contains(room_id)
+        }
+
+        /// The IDs of the rooms that the user is joined to.
+        pub fn joined_rooms(&self) -> impl Iterator<Item = &RoomId> {
+            self.joined.iter().map(|room_id| &**room_id)
+        }
+
+        /// The IDs of the rooms that the user is joined to in this snapshot, but not in the
+        /// `previous` snapshot.
+        pub fn joined_since<'a>(
+            &'a self,
+            previous: &'a MembershipSnapshot,
+        ) -> impl Iterator<Item = &'a RoomId> {
+            self.joined.difference(&previous.joined).map(|room_id| &**room_id)
+        }
+
+        /// The IDs of the rooms that the user was joined to in the `previous` snapshot, but not
+        /// in this snapshot.
+        pub fn left_since<'a>(
+            &'a self,
+            previous: &'a MembershipSnapshot,
+        ) -> impl Iterator<Item = &'a RoomId> {
+            previous.joined.difference(&self.joined).map(|room_id| &**room_id)
+        }
+    }
+
+    impl From<Response> for MembershipSnapshot {
+        fn from(response: Response) -> Self {
+            Self::new(response.joined_rooms)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ruma_common::{owned_room_id, room_id};
+
+        use super::MembershipSnapshot;
+        use crate::sync::sync_events::v3::{JoinedRoom, LeftRoom, Rooms};
+
+        #[test]
+        fn membership_snapshot() {
+            let previous = MembershipSnapshot::new([
+                owned_room_id!("!a:example.org"),
+                owned_room_id!("!b:example.org"),
+            ]);
+
+            let mut rooms = Rooms::new();
+            rooms.join.insert(owned_room_id!("!c:example.org"), JoinedRoom::new());
+            rooms.leave.insert(owned_room_id!("!a:example.org"), LeftRoom::new());
+
+            let mut current = previous.clone();
+            current.apply_sync_rooms(&rooms);
+
+            assert!(!current.is_joined(room_id!("!a:example.org")));
+            assert!(current.is_joined(room_id!("!b:example.org")));
+            assert!(current.is_joined(room_id!("!c:example.org")));
+            assert_eq!(
+                current.joined_since(&previous).collect::<Vec<_>>(),
+                [room_id!("!c:example.org")]
+            );
+            assert_eq!(
+                current.left_since(&previous).collect::<Vec<_>>(),
+                [room_id!("!a:example.org")]
+            );
+        }
+    }
 }
@@ -16,6 +16,7 @@ pub mod v3 {
         method: POST,
         rate_limited: true,
         authentication: AccessToken,
+        guest_access: true,
         history: {
             1.0 => "/_matrix/client/r0/rooms/:room_id/leave",
             1.1 => "/_matrix/client/v3/rooms/:room_id/leave",
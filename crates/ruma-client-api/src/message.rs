@@ -2,3 +2,10 @@
 
 pub mod get_message_events;
 pub mod send_message_event;
+
+/// The maximum size of an event, in bytes.
+///
+/// This limit applies to the complete event as sent over federation, including the fields added by
+/// the homeserver and the signatures. The content of an event sent by a client must then be
+/// smaller than this limit for the event to be accepted.
+pub const MAX_EVENT_SIZE: usize = 65_535;
@@ -7,21 +7,24 @@ pub mod v3 {
     //!
     //! [spec]: https://spec.matrix.org/latest/client-server-api/#get_matrixclientv3roomsroomidmessages
 
+    use std::collections::BTreeSet;
+
     use js_int::{uint, UInt};
     use ruma_common::{
         api::{request, response, Direction, Metadata},
         metadata,
         serde::Raw,
-        OwnedRoomId,
+        EventId, OwnedEventId, OwnedRoomId,
     };
-    use ruma_events::{AnyStateEvent, AnyTimelineEvent};
+    use ruma_events::{AnyStateEvent, AnySyncTimelineEvent, AnyTimelineEvent};
 
-    use crate::filter::RoomEventFilter;
+    use crate::{filter::RoomEventFilter, sync::sync_events, PaginationToken};
 
     const METADATA: Metadata = metadata! {
         method: GET,
         rate_limited: false,
         authentication: AccessToken,
+        guest_access: true,
         history: {
             1.0 => "/_matrix/client/r0/rooms/:room_id/messages",
             1.1 => "/_matrix/client/v3/rooms/:room_id/messages",
@@ -44,7 +47,7 @@ pub mod v3 {
         /// If this is `None`, the server will return messages from the start or end of the
         /// history visible to the user, depending on the value of [`dir`][Self::dir].
         #[ruma_api(query)]
-        pub from: Option<String>,
+        pub from: Option<PaginationToken>,
 
         /// The token to stop returning events at.
         ///
@@ -53,7 +56,7 @@ pub mod v3 {
         /// this endpoint.
         #[serde(skip_serializing_if = "Option::is_none")]
         #[ruma_api(query)]
-        pub to: Option<String>,
+        pub to: Option<PaginationToken>,
 
         /// The direction to return events from.
         #[ruma_api(query)]
@@ -81,11 +84,11 @@ pub mod v3 {
     #[derive(Default)]
     pub struct Response {
         /// The token the pagination starts from.
-        pub start: String,
+        pub start: PaginationToken,
 
         /// The token the pagination ends at.
         #[serde(skip_serializing_if = "Option::is_none")]
-        pub end: Option<String>,
+        pub end: Option<PaginationToken>,
 
         /// A list of room events.
         #[serde(default)]
@@ -119,9 +122,9 @@ pub mod v3 {
         /// # Example
         ///
         /// ```rust
-        /// # use ruma_client_api::message::get_message_events;
+        /// # use ruma_client_api::{message::get_message_events, PaginationToken};
         /// # let room_id = ruma_common::owned_room_id!("!a:example.org");
-        /// # let token = "prev_batch token".to_owned();
+        /// # let token = PaginationToken::from("prev_batch token");
         /// let request = get_message_events::v3::Request::backward(room_id).from(token);
         /// ```
         pub fn backward(room_id: OwnedRoomId) -> Self {
@@ -136,9 +139,9 @@ pub mod v3 {
         /// # Example
         ///
         /// ```rust
-        /// # use ruma_client_api::message::get_message_events;
+        /// # use ruma_client_api::{message::get_message_events, PaginationToken};
         /// # let room_id = ruma_common::owned_room_id!("!a:example.org");
-        /// # let token = "end token".to_owned();
+        /// # let token = PaginationToken::from("end token");
         /// let request = get_message_events::v3::Request::forward(room_id).from(token);
         /// ```
         pub fn forward(room_id: OwnedRoomId) -> Self {
@@ -149,7 +152,7 @@ pub mod v3 {
         ///
         /// Since the field is public, you can also assign to it directly. This method merely acts
         /// as a shorthand for that, because it is very common to set this field.
-        pub fn from(self, from: impl Into<Option<String>>) -> Self {
+        pub fn from(self, from: impl Into<Option<PaginationToken>>) -> Self {
             Self { from: from.into(), ..self }
         }
     }
@@ -161,6 +164,182 @@ pub mod v3 {
         }
     }
 
+    /// A gap in a [`RoomTimeline`], where events are missing.
+    ///
+    /// The gap can be filled by sending a backward [`Request`] with `from` set to
+    /// [`prev_batch`](Self::prev_batch), and passing the response to
+    /// [`RoomTimeline::fill_gap()`].
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+    pub struct TimelineGap {
+        /// The token to paginate backwards from to fill the gap.
+        pub prev_batch: PaginationToken,
+
+        /// The ID of the first known event after the gap, if any.
+        pub before_event: Option<OwnedEventId>,
+    }
+
+    impl TimelineGap {
+        /// Creates a new `TimelineGap` with the given token and event ID.
+        pub fn new(prev_batch: PaginationToken, before_event: Option<OwnedEventId>) -> Self {
+            Self { prev_batch, before_event }
+        }
+    }
+
+    /// An item of a [`RoomTimeline`].
+    #[derive(Clone, Debug)]
+    #[allow(clippy::exhaustive_enums)]
+    pub enum TimelineItem {
+        /// A known event.
+        Event(Raw<AnySyncTimelineEvent>),
+
+        /// A gap where events are missing.
+        Gap(TimelineGap),
+    }
+
+    /// The timeline of a room, made of known events and the gaps between them.
+    ///
+    /// The timeline is built from the [`sync_events`] responses with
+    /// [`append_sync()`](Self::append_sync), which adds a gap every time the sync timeline is
+    /// limited. The gaps are filled by paginating backwards with this endpoint, and passing the
+    /// responses to [`fill_gap()`](Self::fill_gap), until the timeline is
+    /// [gap-free](Self::is_gap_free).
+    ///
+    /// Events are deduplicated by event ID, so overlapping responses can be merged safely.
+    #[derive(Clone, Debug, Default)]
+    pub struct RoomTimeline {
+        items: Vec<TimelineItem>,
+        known_events: BTreeSet<OwnedEventId>,
+    }
+
+    impl RoomTimeline {
+        /// Creates an empty `RoomTimeline`.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// The items of the timeline, in chronological order.
+        pub fn items(&self) -> &[TimelineItem] {
+            &self.items
+        }
+
+        /// The known events of the timeline, in chronological order.
+        pub fn events(&self) -> impl Iterator<Item = &Raw<AnySyncTimelineEvent>> {
+            self.items.iter().filter_map(|item| match item {
+                TimelineItem::Event(event) => Some(event),
+                TimelineItem::Gap(_) => None,
+            })
+        }
+
+        /// The gaps of the timeline, in chronological order.
+        pub fn gaps(&self) -> impl Iterator<Item = &TimelineGap> {
+            self.items.iter().filter_map(|item| match item {
+                TimelineItem::Gap(gap) => Some(gap),
+                TimelineItem::Event(_) => None,
+            })
+        }
+
+        /// Whether the timeline doesn't have any gap.
+        ///
+        /// This means that all the events since the start of the room, or since the first event
+        /// that was received, are known.
+        pub fn is_gap_free(&self) -> bool {
+            self.gaps().next().is_none()
+        }
+
+        /// Whether the event with the given ID is in the timeline.
+        pub fn contains_event(&self, event_id: &EventId) -> bool {
+            self.known_events.contains(event_id)
+        }
+
+        /// Append the timeline of a room from a sync response.
+        ///
+        /// A gap is added before the new events if the timeline is limited, or if it is the first
+        /// timeline that is appended, as long as it has a `prev_batch` token.
+        pub fn append_sync(&mut self, timeline: sync_events::v3::Timeline) {
+            if let Some(prev_batch) = timeline.prev_batch {
+                if timeline.limited || self.items.is_empty() {
+                    let before_event = timeline.events.iter().find_map(event_id);
+                    self.items.push(TimelineItem::Gap(TimelineGap { prev_batch, before_event }));
+                }
+            }
+
+            for event in timeline.events {
+                self.push_event(event);
+            }
+        }
+
+        /// Fill the gap whose token was used as the `from` of a backward [`Request`] with its
+        /// response.
+        ///
+        /// The events of the response are inserted in place of the gap, until an event that is
+        /// already known is reached. If no known event is reached and the response has an `end`
+        /// token, a new gap is kept before the inserted events.
+        ///
+        /// Returns `false` if there is no gap with the given token.
+        pub fn fill_gap(&mut self, from: &PaginationToken, response: Response) -> bool {
+            let Some(index) = self
+                .items
+                .iter()
+                .position(|item| matches!(item, TimelineItem::Gap(gap) if gap.prev_batch == *from))
+            else {
+                return false;
+            };
+
+            let mut events = Vec::new();
+            let mut reached_known_event = false;
+
+            // The events of a backward pagination are in reverse chronological order.
+            for event in response.chunk {
+                let event = event.cast::<AnySyncTimelineEvent>();
+
+                match event_id(&event) {
+                    Some(id) if self.known_events.contains(&id) => {
+                        reached_known_event = true;
+                        break;
+                    }
+                    Some(id) => {
+                        self.known_events.insert(id);
+                    }
+                    None => {}
+                }
+
+                events.push(TimelineItem::Event(event));
+            }
+
+            let remaining_gap = match response.end {
+                Some(end) if !reached_known_event => {
+                    let before_event = match events.last() {
+                        Some(TimelineItem::Event(event)) => event_id(event),
+                        _ => None,
+                    };
+                    Some(TimelineItem::Gap(TimelineGap { prev_batch: end, before_event }))
+                }
+                _ => None,
+            };
+
+            events.extend(remaining_gap);
+            events.reverse();
+            self.items.splice(index..=index, events);
+
+            true
+        }
+
+        fn push_event(&mut self, event: Raw<AnySyncTimelineEvent>) {
+            if let Some(id) = event_id(&event) {
+                if !self.known_events.insert(id) {
+                    return;
+                }
+            }
+
+            self.items.push(TimelineItem::Event(event));
+        }
+    }
+
+    fn event_id(event: &Raw<AnySyncTimelineEvent>) -> Option<OwnedEventId> {
+        event.get_field("event_id").ok().flatten()
+    }
+
     fn default_limit() -> UInt {
         uint!(10)
     }
@@ -174,12 +353,19 @@ pub mod v3 {
     mod tests {
         use js_int::uint;
         use ruma_common::{
-            api::{Direction, MatrixVersion, OutgoingRequest, SendAccessToken},
-            owned_room_id,
+            api::{Direction, IncomingResponse, MatrixVersion, OutgoingRequest, SendAccessToken},
+            event_id, owned_room_id,
+            serde::Raw,
+            EventId,
         };
+        use serde_json::{json, to_vec as to_json_vec};
 
-        use super::Request;
-        use crate::filter::{LazyLoadOptions, RoomEventFilter};
+        use super::{Request, Response, RoomTimeline, TimelineItem};
+        use crate::{
+            filter::{LazyLoadOptions, RoomEventFilter},
+            sync::sync_events,
+            PaginationToken,
+        };
 
         #[test]
         fn serialize_some_room_event_filter() {
@@ -198,8 +384,8 @@ pub mod v3 {
             };
             let req = Request {
                 room_id,
-                from: Some("token".to_owned()),
-                to: Some("token2".to_owned()),
+                from: Some("token".into()),
+                to: Some("token2".into()),
                 dir: Direction::Backward,
                 limit: uint!(0),
                 filter,
@@ -227,8 +413,8 @@ pub mod v3 {
             let room_id = owned_room_id!("!roomid:example.org");
             let req = Request {
                 room_id,
-                from: Some("token".to_owned()),
-                to: Some("token2".to_owned()),
+                from: Some("token".into()),
+                to: Some("token2".into()),
                 dir: Direction::Backward,
                 limit: uint!(0),
                 filter: RoomEventFilter::default(),
@@ -243,5 +429,110 @@ pub mod v3 {
                 .unwrap();
             assert_eq!("from=token&to=token2&dir=b&limit=0", request.uri().query().unwrap(),);
         }
+
+        #[test]
+        fn deserialize_response() {
+            let body = json!({
+                "start": "t47429-4392820_219380_26003_2265",
+                "end": "t47409-4357353_219380_26003_2265",
+                "chunk": [],
+                "state": [
+                    {
+                        "content": {
+                            "membership": "join",
+                        },
+                        "event_id": "$143273582443PhrSn:example.org",
+                        "origin_server_ts": 1_432_735_824_653_u64,
+                        "room_id": "!roomid:example.org",
+                        "sender": "@example:example.org",
+                        "state_key": "@example:example.org",
+                        "type": "m.room.member",
+                    },
+                ],
+            });
+            let response = http::Response::new(to_json_vec(&body).unwrap());
+
+            let response = Response::try_from_http_response(response).unwrap();
+            assert_eq!(response.start, "t47429-4392820_219380_26003_2265");
+            assert_eq!(response.end.unwrap(), "t47409-4357353_219380_26003_2265");
+            assert_eq!(response.chunk.len(), 0);
+            assert_eq!(response.state.len(), 1);
+        }
+
+        fn event<T>(event_id: &EventId) -> Raw<T> {
+            Raw::new(&json!({
+                "content": { "body": "hello", "msgtype": "m.text" },
+                "event_id": event_id,
+                "origin_server_ts": 1_432_735_824_653_u64,
+                "room_id": "!roomid:example.org",
+                "sender": "@example:example.org",
+                "type": "m.room.message",
+            }))
+            .unwrap()
+            .cast()
+        }
+
+        fn event_ids(timeline: &RoomTimeline) -> Vec<String> {
+            timeline
+                .items()
+                .iter()
+                .map(|item| match item {
+                    TimelineItem::Event(ev) => ev.get_field::<String>("event_id").unwrap().unwrap(),
+                    TimelineItem::Gap(gap) => format!("gap:{}", gap.prev_batch),
+                })
+                .collect()
+        }
+
+        #[test]
+        fn room_timeline_fill_gaps() {
+            let mut timeline = RoomTimeline::new();
+
+            let mut sync = sync_events::v3::Timeline::new();
+            sync.prev_batch = Some(PaginationToken::from("p1"));
+            sync.events = vec![event(event_id!("$a")), event(event_id!("$b"))];
+            timeline.append_sync(sync);
+
+            let mut sync = sync_events::v3::Timeline::new();
+            sync.limited = true;
+            sync.prev_batch = Some(PaginationToken::from("p2"));
+            sync.events = vec![event(event_id!("$e")), event(event_id!("$f"))];
+            timeline.append_sync(sync);
+
+            assert_eq!(event_ids(&timeline), ["gap:p1", "$a", "$b", "gap:p2", "$e", "$f"]);
+            assert_eq!(
+                timeline.gaps().next().unwrap().before_event.as_deref(),
+                Some(event_id!("$a"))
+            );
+
+            // Partially fill the second gap.
+            let mut response = Response::new();
+            response.chunk = vec![event(event_id!("$d"))];
+            response.end = Some(PaginationToken::from("p3"));
+            assert!(timeline.fill_gap(&PaginationToken::from("p2"), response));
+            assert_eq!(event_ids(&timeline), ["gap:p1", "$a", "$b", "gap:p3", "$d", "$e", "$f"]);
+
+            // Fill the rest of the gap, until a known event is reached.
+            let mut response = Response::new();
+            response.chunk =
+                vec![event(event_id!("$c")), event(event_id!("$b")), event(event_id!("$a"))];
+            response.end = Some(PaginationToken::from("p4"));
+            assert!(timeline.fill_gap(&PaginationToken::from("p3"), response));
+            assert_eq!(event_ids(&timeline), ["gap:p1", "$a", "$b", "$c", "$d", "$e", "$f"]);
+            assert!(!timeline.fill_gap(&PaginationToken::from("p3"), Response::new()));
+
+            // Reach the start of the room.
+            let mut response = Response::new();
+            response.chunk = vec![event(event_id!("$create"))];
+            assert!(timeline.fill_gap(&PaginationToken::from("p1"), response));
+            assert_eq!(event_ids(&timeline), ["$create", "$a", "$b", "$c", "$d", "$e", "$f"]);
+            assert!(timeline.is_gap_free());
+
+            // Events from a later sync are deduplicated.
+            let mut sync = sync_events::v3::Timeline::new();
+            sync.events = vec![event(event_id!("$f")), event(event_id!("$g"))];
+            timeline.append_sync(sync);
+            assert_eq!(timeline.events().count(), 8);
+            assert!(timeline.contains_event(event_id!("$g")));
+        }
     }
 }
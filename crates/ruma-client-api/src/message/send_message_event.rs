@@ -1,6 +1,10 @@
 //! `PUT /_matrix/client/*/rooms/{roomId}/send/{eventType}/{txnId}`
 //!
 //! Send a message event to a room.
+//!
+//! Guest accounts are only allowed to send `m.room.message` events, so this endpoint is not marked
+//! as allowing guest access in its metadata. Servers that accept requests from guests must check
+//! the event type themselves.
 
 pub mod v3 {
     //! `/v3/` ([spec])
@@ -106,6 +110,22 @@ pub mod v3 {
         ) -> Self {
             Self { room_id, event_type, txn_id, body, timestamp: None }
         }
+
+        /// The size of the body of this request, in bytes.
+        ///
+        /// This is the size of the event content, which is already serialized.
+        pub fn body_size(&self) -> usize {
+            self.body.json().get().len()
+        }
+
+        /// Whether the event content of this request is too large to fit in an event.
+        ///
+        /// Such a request would be rejected by the homeserver, so it should not be sent. An event
+        /// might still be rejected if its content is slightly smaller than the limit, because of
+        /// the other fields of the event.
+        pub fn exceeds_max_event_size(&self) -> bool {
+            self.body_size() > crate::message::MAX_EVENT_SIZE
+        }
     }
 
     impl Response {
@@ -114,4 +134,34 @@ pub mod v3 {
             Self { event_id }
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use ruma_common::{owned_room_id, OwnedTransactionId};
+        use ruma_events::room::message::RoomMessageEventContent;
+
+        use super::Request;
+
+        #[test]
+        fn body_size() {
+            let content = RoomMessageEventContent::text_plain("Hello");
+            let request = Request::new(
+                owned_room_id!("!room:localhost"),
+                OwnedTransactionId::from("1"),
+                &content,
+            )
+            .unwrap();
+            assert_eq!(request.body_size(), serde_json::to_string(&content).unwrap().len());
+            assert!(!request.exceeds_max_event_size());
+
+            let content = RoomMessageEventContent::text_plain("a".repeat(70_000));
+            let request = Request::new(
+                owned_room_id!("!room:localhost"),
+                OwnedTransactionId::from("1"),
+                &content,
+            )
+            .unwrap();
+            assert!(request.exceeds_max_event_size());
+        }
+    }
 }
@@ -18,6 +18,7 @@ pub mod v3 {
         method: PUT,
         rate_limited: true,
         authentication: AccessToken,
+        guest_access: true,
         history: {
             1.0 => "/_matrix/client/r0/presence/:user_id/status",
             1.1 => "/_matrix/client/v3/presence/:user_id/status",
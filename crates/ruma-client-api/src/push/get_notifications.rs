@@ -18,6 +18,8 @@ pub mod v3 {
     use ruma_events::AnySyncTimelineEvent;
     use serde::{Deserialize, Serialize};
 
+    use crate::PaginationToken;
+
     const METADATA: Metadata = metadata! {
         method: GET,
         rate_limited: false,
@@ -35,7 +37,7 @@ pub mod v3 {
         /// Pagination token given to retrieve the next set of events.
         #[ruma_api(query)]
         #[serde(skip_serializing_if = "Option::is_none")]
-        pub from: Option<String>,
+        pub from: Option<PaginationToken>,
 
         /// Limit on the number of events to return in this request.
         #[ruma_api(query)]
@@ -59,7 +61,7 @@ pub mod v3 {
         ///
         /// If this is absent, there are no more results.
         #[serde(skip_serializing_if = "Option::is_none")]
-        pub next_token: Option<String>,
+        pub next_token: Option<PaginationToken>,
 
         /// The list of events that triggered notifications.
         pub notifications: Vec<Notification>,
@@ -19,6 +19,7 @@ pub mod v3 {
         method: GET,
         rate_limited: false,
         authentication: AccessToken,
+        guest_access: true,
         history: {
             1.0 => "/_matrix/client/r0/rooms/:room_id/event/:event_id",
             1.1 => "/_matrix/client/v3/rooms/:room_id/event/:event_id",
@@ -1,4 +1,4 @@
-//! `GET /_matrix/client/v1/summary/{roomIdOrAlias}`
+//! `GET /_matrix/client/v1/room_summary/{roomIdOrAlias}`
 //!
 //! Experimental API enabled with MSC3266.
 //!
@@ -26,6 +26,7 @@ pub mod msc3266 {
         authentication: AccessTokenOptional,
         history: {
             unstable => "/_matrix/client/unstable/im.nheko.summary/rooms/:room_id_or_alias/summary",
+            unstable => "/_matrix/client/unstable/im.nheko.summary/summary/:room_id_or_alias",
         }
     };
 
@@ -83,7 +84,7 @@ pub mod msc3266 {
         pub room_type: Option<RoomType>,
 
         /// Version of the room.
-        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(alias = "im.nheko.summary.room_version", skip_serializing_if = "Option::is_none")]
         pub room_version: Option<RoomVersionId>,
 
         /// The current membership of this user in the room.
@@ -95,7 +96,7 @@ pub mod msc3266 {
         pub membership: Option<MembershipState>,
 
         /// If the room is encrypted, the algorithm used for this room.
-        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(alias = "im.nheko.summary.encryption", skip_serializing_if = "Option::is_none")]
         pub encryption: Option<EventEncryptionAlgorithm>,
     }
 
@@ -132,4 +133,56 @@ pub mod msc3266 {
             }
         }
     }
+
+    #[cfg(all(test, feature = "client"))]
+    mod tests {
+        use ruma_common::{
+            api::{IncomingResponse, MatrixVersion, OutgoingRequest, SendAccessToken},
+            owned_server_name,
+            space::SpaceRoomJoinRule,
+            EventEncryptionAlgorithm, OwnedRoomOrAliasId, RoomVersionId,
+        };
+        use serde_json::{json, to_vec as to_json_vec};
+
+        use super::{Request, Response};
+
+        #[test]
+        fn serialize_request() {
+            let req = Request::new(
+                OwnedRoomOrAliasId::try_from("#room:example.org").unwrap(),
+                vec![owned_server_name!("example.org")],
+            )
+            .try_into_http_request::<Vec<u8>>(
+                "https://homeserver.tld",
+                SendAccessToken::IfRequired("auth_tok"),
+                &[MatrixVersion::V1_1],
+            )
+            .unwrap();
+
+            assert_eq!(
+                req.uri(),
+                "https://homeserver.tld/_matrix/client/unstable/im.nheko.summary/summary/%23room:example.org?via=example.org"
+            );
+        }
+
+        #[test]
+        fn deserialize_response_unstable_fields() {
+            let body = json!({
+                "room_id": "!room:example.org",
+                "guest_can_join": false,
+                "num_joined_members": 5,
+                "world_readable": true,
+                "join_rule": "public",
+                "im.nheko.summary.room_version": "10",
+                "im.nheko.summary.encryption": "m.megolm.v1.aes-sha2",
+            });
+            let response = http::Response::new(to_json_vec(&body).unwrap());
+
+            let response = Response::try_from_http_response(response).unwrap();
+            assert_eq!(response.room_id, "!room:example.org");
+            assert_eq!(response.join_rule, SpaceRoomJoinRule::Public);
+            assert_eq!(response.room_version, Some(RoomVersionId::V10));
+            assert_eq!(response.encryption, Some(EventEncryptionAlgorithm::MegolmV1AesSha2));
+        }
+    }
 }
@@ -90,6 +90,22 @@ pub mod v3 {
         ) -> Self {
             Self { room_id, event_type, state_key, body, timestamp: None }
         }
+
+        /// The size of the body of this request, in bytes.
+        ///
+        /// This is the size of the event content, which is already serialized.
+        pub fn body_size(&self) -> usize {
+            self.body.json().get().len()
+        }
+
+        /// Whether the event content of this request is too large to fit in an event.
+        ///
+        /// Such a request would be rejected by the homeserver, so it should not be sent. An event
+        /// might still be rejected if its content is slightly smaller than the limit, because of
+        /// the other fields of the event.
+        pub fn exceeds_max_event_size(&self) -> bool {
+            self.body_size() > crate::message::MAX_EVENT_SIZE
+        }
     }
 
     /// Response type for the `send_state_event` endpoint.
@@ -20,12 +20,13 @@ use ruma_events::{
 use serde::{Deserialize, Serialize};
 
 use super::{DeviceLists, UnreadNotificationsCount};
-use crate::filter::FilterDefinition;
+use crate::{filter::FilterDefinition, PaginationToken, SyncToken};
 
 const METADATA: Metadata = metadata! {
     method: GET,
     rate_limited: false,
     authentication: AccessToken,
+    guest_access: true,
     history: {
         1.0 => "/_matrix/client/r0/sync",
         1.1 => "/_matrix/client/v3/sync",
@@ -47,7 +48,7 @@ pub struct Request {
     /// request.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[ruma_api(query)]
-    pub since: Option<String>,
+    pub since: Option<SyncToken>,
 
     /// Controls whether to include the full state for all rooms the user is a member of.
     #[serde(default, skip_serializing_if = "ruma_common::serde::is_default")]
@@ -75,7 +76,7 @@ pub struct Request {
 #[response(error = crate::Error)]
 pub struct Response {
     /// The batch token to supply in the `since` param of the next `/sync` request.
-    pub next_batch: String,
+    pub next_batch: SyncToken,
 
     /// Updates to rooms.
     #[serde(default, skip_serializing_if = "Rooms::is_empty")]
@@ -121,7 +122,7 @@ impl Request {
 
 impl Response {
     /// Creates a new `Response` with the given batch token.
-    pub fn new(next_batch: String) -> Self {
+    pub fn new(next_batch: SyncToken) -> Self {
         Self {
             next_batch,
             rooms: Default::default(),
@@ -346,7 +347,7 @@ pub struct Timeline {
     /// A token that can be supplied to to the `from` parameter of the
     /// `/rooms/{roomId}/messages` endpoint.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub prev_batch: Option<String>,
+    pub prev_batch: Option<PaginationToken>,
 
     /// A list of events.
     pub events: Vec<Raw<AnySyncTimelineEvent>>,
@@ -632,7 +633,7 @@ mod client_tests {
     fn serialize_all_params() {
         let req: http::Request<Vec<u8>> = Request {
             filter: Some(Filter::FilterId("66696p746572".to_owned())),
-            since: Some("s72594_4483_1934".to_owned()),
+            since: Some("s72594_4483_1934".into()),
             full_state: true,
             set_presence: PresenceState::Offline,
             timeout: Some(Duration::from_millis(30000)),
@@ -689,7 +690,7 @@ mod server_tests {
 
         assert_matches!(req.filter, Some(Filter::FilterId(id)));
         assert_eq!(id, "myfilter");
-        assert_eq!(req.since.as_deref(), Some("myts"));
+        assert_eq!(req.since.unwrap(), "myts");
         assert!(!req.full_state);
         assert_eq!(req.set_presence, PresenceState::Offline);
         assert_eq!(req.timeout, Some(Duration::from_millis(5000)));
@@ -646,7 +646,11 @@ pub struct ToDeviceConfig {
 impl ToDeviceConfig {
     /// Whether all fields are empty or `None`.
     pub fn is_empty(&self) -> bool {
-        self.enabled.is_none() && self.limit.is_none() && self.since.is_none()
+        self.enabled.is_none()
+            && self.limit.is_none()
+            && self.since.is_none()
+            && self.lists.is_none()
+            && self.rooms.is_none()
     }
 }
 
@@ -754,7 +758,7 @@ pub struct AccountDataConfig {
 impl AccountDataConfig {
     /// Whether all fields are empty or `None`.
     pub fn is_empty(&self) -> bool {
-        self.enabled.is_none()
+        self.enabled.is_none() && self.lists.is_none() && self.rooms.is_none()
     }
 }
 
@@ -849,7 +853,7 @@ pub struct ReceiptsConfig {
 impl ReceiptsConfig {
     /// Whether all fields are empty or `None`.
     pub fn is_empty(&self) -> bool {
-        self.enabled.is_none()
+        self.enabled.is_none() && self.lists.is_none() && self.rooms.is_none()
     }
 }
 
@@ -904,7 +908,7 @@ pub struct TypingConfig {
 impl TypingConfig {
     /// Whether all fields are empty or `None`.
     pub fn is_empty(&self) -> bool {
-        self.enabled.is_none()
+        self.enabled.is_none() && self.lists.is_none() && self.rooms.is_none()
     }
 }
 
@@ -930,8 +934,9 @@ impl Typing {
 #[cfg(test)]
 mod tests {
     use ruma_common::owned_room_id;
+    use serde_json::json;
 
-    use crate::sync::sync_events::v4::RoomReceiptConfig;
+    use crate::sync::sync_events::v4::{ExtensionsConfig, RoomReceiptConfig};
 
     #[test]
     fn serialize_room_receipt_config() {
@@ -954,4 +959,24 @@ mod tests {
             RoomReceiptConfig::Room(owned_room_id!("!n8f893n9:example.com"))
         );
     }
+
+    #[test]
+    fn serialize_sticky_extension_lists_and_rooms() {
+        let mut extensions = ExtensionsConfig::default();
+        extensions.to_device.lists = Some(vec!["all_rooms".to_owned()]);
+        extensions.account_data.rooms = Some(vec![owned_room_id!("!n8f893n9:example.com")]);
+        extensions.receipts.rooms = Some(vec![RoomReceiptConfig::AllSubscribed]);
+        extensions.typing.lists = Some(vec![]);
+
+        assert!(!extensions.is_empty());
+        assert_eq!(
+            serde_json::to_value(&extensions).unwrap(),
+            json!({
+                "to_device": { "lists": ["all_rooms"] },
+                "account_data": { "rooms": ["!n8f893n9:example.com"] },
+                "receipts": { "rooms": ["*"] },
+                "typing": { "lists": [] },
+            })
+        );
+    }
 }
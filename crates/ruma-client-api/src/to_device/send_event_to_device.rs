@@ -22,6 +22,7 @@ pub mod v3 {
         method: PUT,
         rate_limited: false,
         authentication: AccessToken,
+        guest_access: true,
         history: {
             1.0 => "/_matrix/client/r0/sendToDevice/:event_type/:txn_id",
             1.1 => "/_matrix/client/v3/sendToDevice/:event_type/:txn_id",
@@ -0,0 +1,97 @@
+//! Opaque tokens returned by the homeserver.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+macro_rules! token_type {
+    (
+        $( #[doc = $docs:literal] )*
+        pub struct $id:ident;
+    ) => {
+        $( #[doc = $docs] )*
+        #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+        #[serde(transparent)]
+        #[allow(clippy::exhaustive_structs)]
+        pub struct $id(String);
+
+        impl $id {
+            #[doc = concat!("Creates a string slice from this `", stringify!($id), "`.")]
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            #[doc = concat!("Converts this `", stringify!($id), "` into a `String`.")]
+            pub fn into_string(self) -> String {
+                self.0
+            }
+        }
+
+        impl AsRef<str> for $id {
+            fn as_ref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl From<String> for $id {
+            fn from(token: String) -> Self {
+                Self(token)
+            }
+        }
+
+        impl From<&str> for $id {
+            fn from(token: &str) -> Self {
+                Self(token.to_owned())
+            }
+        }
+
+        impl From<$id> for String {
+            fn from(token: $id) -> Self {
+                token.0
+            }
+        }
+
+        impl fmt::Display for $id {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl PartialEq<str> for $id {
+            fn eq(&self, other: &str) -> bool {
+                self.as_str() == other
+            }
+        }
+
+        impl PartialEq<&str> for $id {
+            fn eq(&self, other: &&str) -> bool {
+                self.as_str() == *other
+            }
+        }
+
+        impl PartialEq<String> for $id {
+            fn eq(&self, other: &String) -> bool {
+                self.as_str() == other
+            }
+        }
+    };
+}
+
+token_type! {
+    /// A token to paginate through a list of items.
+    ///
+    /// Pagination tokens are returned by the homeserver in the responses of endpoints that support
+    /// pagination, and can be used in subsequent requests to fetch the next or previous chunk of
+    /// items. They are opaque strings, this type is provided simply for its semantic value.
+    pub struct PaginationToken;
+}
+
+token_type! {
+    /// A token representing a position in the stream of events of the homeserver.
+    ///
+    /// Sync tokens are returned by the homeserver in the `next_batch` field of the response of
+    /// `/sync`, and can be used as the `since` parameter of the next `/sync` request, or with
+    /// endpoints like `/keys/changes` that return changes between two positions in the stream.
+    /// They are opaque strings, this type is provided simply for its semantic value.
+    pub struct SyncToken;
+}
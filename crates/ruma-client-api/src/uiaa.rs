@@ -424,6 +424,22 @@ impl fmt::Debug for RegistrationToken {
     }
 }
 
+/// The name of the function that the UIAA fallback page calls on its `window` when the stage is
+/// completed, if it is defined.
+///
+/// See [the spec] for how to use this.
+///
+/// [the spec]: https://spec.matrix.org/latest/client-server-api/#fallback
+pub const FALLBACK_ON_AUTH_DONE: &str = "onAuthDone";
+
+/// The message that the UIAA fallback page posts to its `window.opener` when the stage is
+/// completed, if [`FALLBACK_ON_AUTH_DONE`] is not defined.
+///
+/// See [the spec] for how to use this.
+///
+/// [the spec]: https://spec.matrix.org/latest/client-server-api/#fallback
+pub const FALLBACK_AUTH_DONE_MESSAGE: &str = "authDone";
+
 /// Data for UIAA fallback acknowledgement.
 ///
 /// See [the spec] for how to use this.
@@ -713,7 +729,7 @@ impl OutgoingResponse for UiaaResponse {
         match self {
             UiaaResponse::AuthResponse(authentication_info) => http::Response::builder()
                 .header(http::header::CONTENT_TYPE, "application/json")
-                .status(&http::StatusCode::UNAUTHORIZED)
+                .status(http::StatusCode::UNAUTHORIZED)
                 .body(ruma_common::serde::json_to_buf(&authentication_info)?)
                 .map_err(Into::into),
             UiaaResponse::MatrixError(error) => error.try_into_http_response(),
@@ -7,6 +7,8 @@ pub mod v3 {
     //!
     //! [spec]: https://spec.matrix.org/latest/client-server-api/#fallback
 
+    #[cfg(feature = "client")]
+    use ruma_common::api::{MatrixVersion, OutgoingRequest, SendAccessToken};
     use ruma_common::{
         api::{request, Metadata},
         metadata,
@@ -39,6 +41,31 @@ pub mod v3 {
         pub fn new(auth_type: String, session: String) -> Self {
             Self { auth_type, session }
         }
+
+        /// The URL of the fallback page on the given homeserver, to open in a web browser or a
+        /// webview.
+        ///
+        /// When the stage is completed, the page calls [`FALLBACK_ON_AUTH_DONE`] or posts
+        /// [`FALLBACK_AUTH_DONE_MESSAGE`] to its opener, and the client should then retry the
+        /// request with a [`FallbackAcknowledgement`].
+        ///
+        /// [`FALLBACK_ON_AUTH_DONE`]: crate::uiaa::FALLBACK_ON_AUTH_DONE
+        /// [`FALLBACK_AUTH_DONE_MESSAGE`]: crate::uiaa::FALLBACK_AUTH_DONE_MESSAGE
+        /// [`FallbackAcknowledgement`]: crate::uiaa::FallbackAcknowledgement
+        #[cfg(feature = "client")]
+        pub fn fallback_url(
+            self,
+            homeserver_url: &str,
+            supported_versions: &[MatrixVersion],
+        ) -> Result<String, ruma_common::api::error::IntoHttpError> {
+            let http_request = self.try_into_http_request::<Vec<u8>>(
+                homeserver_url,
+                SendAccessToken::None,
+                supported_versions,
+            )?;
+
+            Ok(http_request.uri().to_string())
+        }
     }
 
     /// Response type for the `authorize_fallback` endpoint.
@@ -147,6 +174,23 @@ pub mod v3 {
 
         use super::Response;
 
+        #[cfg(feature = "client")]
+        #[test]
+        fn fallback_url() {
+            use ruma_common::api::MatrixVersion;
+
+            use super::Request;
+
+            let url = Request::new("m.login.recaptcha".to_owned(), "abc/def".to_owned())
+                .fallback_url("https://homeserver.tld", &[MatrixVersion::V1_1])
+                .unwrap();
+
+            assert_eq!(
+                url,
+                "https://homeserver.tld/_matrix/client/v3/auth/m.login.recaptcha/fallback/web?session=abc%2Fdef"
+            );
+        }
+
         #[cfg(feature = "client")]
         #[test]
         fn incoming_redirect() {
@@ -1,4 +1,5 @@
 use std::{
+    future::Future,
     sync::{Arc, Mutex},
     time::Duration,
 };
@@ -8,23 +9,28 @@ use async_stream::try_stream;
 use futures_core::stream::Stream;
 use ruma_client_api::{
     account::register::{self, RegistrationKind},
+    error::{ErrorKind, RetryAfter},
+    message::get_message_events,
     session::login::{self, v3::LoginInfo},
     sync::sync_events,
     uiaa::UserIdentifier,
+    SyncToken,
 };
 use ruma_common::{
     api::{MatrixVersion, OutgoingRequest, SendAccessToken},
     presence::PresenceState,
     DeviceId, UserId,
 };
+use web_time::SystemTime;
 
 use crate::{
     add_user_id_to_query, send_customized_request, Error, HttpClient, ResponseError, ResponseResult,
 };
 
 mod builder;
+mod paginate;
 
-pub use self::builder::ClientBuilder;
+pub use self::{builder::ClientBuilder, paginate::Paginated};
 
 /// A client for the Matrix client-server API.
 #[derive(Clone, Debug)]
@@ -95,6 +101,54 @@ impl<C: HttpClient> Client<C> {
         .await
     }
 
+    /// Makes a request to a Matrix API endpoint, retrying it if the homeserver responds with a
+    /// `M_LIMIT_EXCEEDED` error.
+    ///
+    /// Before retrying, this waits for the delay requested by the homeserver, or for
+    /// [`DEFAULT_RETRY_DELAY`] if the homeserver didn't provide one, using the `sleep` function.
+    /// This allows to use the timer of any async runtime, for example `tokio::time::sleep`.
+    ///
+    /// After `max_retries` retries, the last error is returned.
+    pub async fn send_request_with_retry<R, S, F>(
+        &self,
+        request: R,
+        max_retries: u32,
+        sleep: S,
+    ) -> ResponseResult<C, R>
+    where
+        R: OutgoingRequest<EndpointError = ruma_client_api::Error> + Clone,
+        S: Fn(Duration) -> F,
+        F: Future<Output = ()>,
+    {
+        let mut retries = 0;
+
+        loop {
+            let error = match self.send_request(request.clone()).await {
+                Ok(response) => return Ok(response),
+                Err(error) => error,
+            };
+
+            let Some(ErrorKind::LimitExceeded { retry_after }) = error.error_kind() else {
+                return Err(error);
+            };
+
+            if retries >= max_retries {
+                return Err(error);
+            }
+
+            let delay = match retry_after {
+                Some(RetryAfter::Delay(delay)) => *delay,
+                Some(RetryAfter::DateTime(time)) => {
+                    time.duration_since(SystemTime::now()).unwrap_or_default()
+                }
+                None => DEFAULT_RETRY_DELAY,
+            };
+
+            retries += 1;
+            sleep(delay).await;
+        }
+    }
+
     /// Makes a request to a Matrix API endpoint as a virtual user.
     ///
     /// This method is meant to be used by application services when interacting with the
@@ -189,7 +243,7 @@ impl<C: HttpClient> Client<C> {
     /// #     .homeserver_url(homeserver_url)
     /// #     .build::<ruma_client::http_client::Dummy>()
     /// #     .await?;
-    /// # let next_batch_token = String::new();
+    /// # let next_batch_token = ruma_client_api::SyncToken::default();
     /// let mut sync_stream = Box::pin(client.sync(
     ///     None,
     ///     next_batch_token,
@@ -205,7 +259,7 @@ impl<C: HttpClient> Client<C> {
     pub fn sync(
         &self,
         filter: Option<sync_events::v3::Filter>,
-        mut since: String,
+        mut since: SyncToken,
         set_presence: PresenceState,
         timeout: Option<Duration>,
     ) -> impl Stream<Item = Result<sync_events::v3::Response, Error<C::Error, ruma_client_api::Error>>>
@@ -226,4 +280,90 @@ impl<C: HttpClient> Client<C> {
             }
         }
     }
+
+    /// Convenience method that represents repeated calls to a paginated endpoint as a stream.
+    ///
+    /// The given request is used for the first call, and its token is updated with the token of
+    /// the next chunk from every response for the following calls. The stream ends when the
+    /// homeserver doesn't return a token for the next chunk anymore, meaning that there are no more
+    /// results to return.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// # use ruma_common::owned_room_id;
+    /// # use tokio_stream::{StreamExt as _};
+    /// # let homeserver_url = "https://example.com".to_owned();
+    /// # async {
+    /// # let client = ruma_client::Client::builder()
+    /// #     .homeserver_url(homeserver_url)
+    /// #     .build::<ruma_client::http_client::Dummy>()
+    /// #     .await?;
+    /// use ruma_client_api::space::get_hierarchy;
+    ///
+    /// let request = get_hierarchy::v1::Request::new(owned_room_id!("!space:example.com"));
+    /// let mut hierarchy_stream = Box::pin(client.paginate(request));
+    /// while let Some(response) = hierarchy_stream.try_next().await? {
+    ///     // Do something with the rooms in the response...
+    /// }
+    /// # Result::<(), ruma_client::Error<_, _>>::Ok(())
+    /// # };
+    /// ```
+    pub fn paginate<R: Paginated + 'static>(
+        &self,
+        mut request: R,
+    ) -> impl Stream<Item = ResponseResult<C, R>> + '_ {
+        try_stream! {
+            loop {
+                let response = self.send_request(request.clone()).await?;
+
+                let next_token = R::next_token(&response);
+                yield response;
+
+                match next_token {
+                    Some(token) => request.set_token(token),
+                    None => break,
+                }
+            }
+        }
+    }
+
+    /// Convenience method that represents repeated calls to the get_message_events endpoint as a
+    /// stream.
+    ///
+    /// This is the same as calling [`Client::paginate()`] with a `get_message_events` request.
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// # use ruma_common::owned_room_id;
+    /// # use tokio_stream::{StreamExt as _};
+    /// # let homeserver_url = "https://example.com".to_owned();
+    /// # async {
+    /// # let client = ruma_client::Client::builder()
+    /// #     .homeserver_url(homeserver_url)
+    /// #     .build::<ruma_client::http_client::Dummy>()
+    /// #     .await?;
+    /// use ruma_client_api::message::get_message_events;
+    ///
+    /// let request = get_message_events::v3::Request::backward(owned_room_id!("!room:example.com"));
+    /// let mut messages_stream = Box::pin(client.messages(request));
+    /// while let Some(response) = messages_stream.try_next().await? {
+    ///     // Do something with the events in the response...
+    /// }
+    /// # Result::<(), ruma_client::Error<_, _>>::Ok(())
+    /// # };
+    /// ```
+    pub fn messages(
+        &self,
+        request: get_message_events::v3::Request,
+    ) -> impl Stream<
+        Item = Result<get_message_events::v3::Response, Error<C::Error, ruma_client_api::Error>>,
+    > + '_ {
+        self.paginate(request)
+    }
 }
+
+/// The delay to wait before retrying a request in [`Client::send_request_with_retry()`], when the
+/// homeserver doesn't provide one.
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
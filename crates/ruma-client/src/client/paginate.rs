@@ -0,0 +1,59 @@
+use ruma_client_api::{
+    directory::{get_public_rooms, get_public_rooms_filtered},
+    message::get_message_events,
+    push::get_notifications,
+    relations::get_relating_events,
+    space::get_hierarchy,
+    threads::get_threads,
+    PaginationToken,
+};
+use ruma_common::api::OutgoingRequest;
+
+/// A request to an endpoint that returns its results in chunks.
+///
+/// The response to such a request contains a token to request the next chunk, which is set on the
+/// request to get the following response. See [`Client::paginate()`](crate::Client::paginate).
+///
+/// Endpoints that return all their results in a single response, like
+/// [`get_member_events`](ruma_client_api::membership::get_member_events), don't implement this
+/// trait.
+pub trait Paginated: OutgoingRequest + Clone {
+    /// The type of the token used to request the next chunk.
+    type Token;
+
+    /// Get the token to request the chunk after the given response.
+    ///
+    /// Returns `None` if there are no more results.
+    fn next_token(response: &Self::IncomingResponse) -> Option<Self::Token>;
+
+    /// Set the token of the chunk to request.
+    fn set_token(&mut self, token: Self::Token);
+}
+
+macro_rules! impl_paginated {
+    ($( $request:ty => $token_ty:ty, $request_field:ident, $response_field:ident; )*) => {
+        $(
+            impl Paginated for $request {
+                type Token = $token_ty;
+
+                fn next_token(response: &Self::IncomingResponse) -> Option<Self::Token> {
+                    response.$response_field.clone()
+                }
+
+                fn set_token(&mut self, token: Self::Token) {
+                    self.$request_field = Some(token);
+                }
+            }
+        )*
+    };
+}
+
+impl_paginated! {
+    get_message_events::v3::Request => PaginationToken, from, end;
+    get_hierarchy::v1::Request => String, from, next_batch;
+    get_relating_events::v1::Request => String, from, next_batch;
+    get_threads::v1::Request => String, from, next_batch;
+    get_notifications::v3::Request => PaginationToken, from, next_token;
+    get_public_rooms::v3::Request => String, since, next_batch;
+    get_public_rooms_filtered::v3::Request => String, since, next_batch;
+}
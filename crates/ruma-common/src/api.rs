@@ -12,7 +12,7 @@
 //!
 //! [apis]: https://spec.matrix.org/latest/#matrix-apis
 
-use std::{convert::TryInto as _, error::Error as StdError};
+use std::{borrow::Cow, convert::TryInto as _, error::Error as StdError};
 
 use as_variant::as_variant;
 use bytes::BufMut;
@@ -31,6 +31,7 @@ use crate::UserId;
 ///     method: GET, // one of the associated constants of http::Method
 ///     rate_limited: true,
 ///     authentication: AccessToken, // one of the variants of api::AuthScheme
+///     guest_access: true, // optional, defaults to false
 ///
 ///     // history of endpoint paths
 ///     // there must be at least one path but otherwise everything is optional
@@ -47,8 +48,26 @@ use crate::UserId;
 #[macro_export]
 macro_rules! metadata {
     ( $( $field:ident: $rhs:tt ),+ $(,)? ) => {
+        $crate::metadata!(@fields [] [false] $( $field: $rhs, )+)
+    };
+
+    ( @fields [ $($fields:tt)* ] [ $_guest_access:expr ] guest_access: $value:tt, $($rest:tt)* ) => {
+        $crate::metadata!(@fields [ $($fields)* ] [ $value ] $($rest)*)
+    };
+
+    ( @fields [ $($fields:tt)* ] [ $guest_access:expr ] $field:ident: $rhs:tt, $($rest:tt)* ) => {
+        $crate::metadata!(
+            @fields
+            [ $($fields)* $field: $crate::metadata!(@field $field: $rhs), ]
+            [ $guest_access ]
+            $($rest)*
+        )
+    };
+
+    ( @fields [ $($fields:tt)* ] [ $guest_access:expr ] ) => {
         $crate::api::Metadata {
-            $( $field: $crate::metadata!(@field $field: $rhs) ),+
+            $($fields)*
+            guest_access: $guest_access,
         }
     };
 
@@ -473,6 +492,30 @@ pub trait IncomingRequest: Sized {
     where
         B: AsRef<[u8]>,
         S: AsRef<str>;
+
+    /// Tries to turn the given `http::Request` into this request type, if its method and path
+    /// match this endpoint.
+    ///
+    /// The path arguments are extracted from the URI of the request with
+    /// [`Metadata::match_path`]. Returns `Ok(None)` if the request is for another endpoint, which
+    /// allows to try several request types in turn to route incoming requests.
+    fn try_from_matching_http_request<B>(
+        req: http::Request<B>,
+    ) -> Result<Option<Self>, FromHttpRequestError>
+    where
+        B: AsRef<[u8]>,
+    {
+        if req.method() != Self::METADATA.method {
+            return Ok(None);
+        }
+
+        let Some(path_args) = Self::METADATA.match_path(req.uri().path()) else {
+            return Ok(None);
+        };
+        let path_args: Vec<String> = path_args.into_iter().map(Cow::into_owned).collect();
+
+        Self::try_from_http_request(req, &path_args).map(Some)
+    }
 }
 
 /// A request type for a Matrix API endpoint, used for sending responses.
@@ -538,3 +581,102 @@ pub enum Direction {
     #[serde(rename = "f")]
     Forward,
 }
+
+#[doc(hidden)]
+pub mod __private {
+    /// A guard for the span around the conversion of a request or response.
+    ///
+    /// The span is only created if the `tracing-spans` feature is enabled.
+    #[must_use]
+    #[allow(missing_debug_implementations, clippy::exhaustive_structs)]
+    pub struct ConversionSpan {
+        #[cfg(feature = "tracing-spans")]
+        _span: tracing::span::EnteredSpan,
+    }
+
+    /// Enter the span around the conversion of a request or response.
+    ///
+    /// The span is named `ruma_api`, and has the following fields:
+    ///
+    /// * `ruma.operation`: the name of the conversion method, e.g. `try_into_http_request`.
+    /// * `ruma.type_name`: the full path of the type of the request or response.
+    #[cfg_attr(not(feature = "tracing-spans"), allow(unused_variables))]
+    pub fn enter_conversion_span(
+        operation: &'static str,
+        type_name: &'static str,
+    ) -> ConversionSpan {
+        ConversionSpan {
+            #[cfg(feature = "tracing-spans")]
+            _span: tracing::debug_span!(
+                "ruma_api",
+                ruma.operation = operation,
+                ruma.type_name = type_name,
+            )
+            .entered(),
+        }
+    }
+
+    /// Normalize the query string of an incoming request before it is deserialized.
+    ///
+    /// Clients often use the bracketed syntax of web frameworks for repeated keys, like
+    /// `server[]=a&server[]=b` or `server[0]=a&server[1]=b`. This removes the brackets so those
+    /// keys are deserialized like `server=a&server=b`, with indexed values sorted by their index.
+    ///
+    /// Keys with other brackets, like `filter[limit]`, are kept as-is.
+    pub fn normalize_query(query: &str) -> std::borrow::Cow<'_, str> {
+        use std::borrow::Cow;
+
+        let has_bracket = query.contains('[')
+            || query.as_bytes().windows(3).any(|w| w.eq_ignore_ascii_case(b"%5b"));
+        if !has_bracket {
+            return Cow::Borrowed(query);
+        }
+
+        let mut has_index = false;
+        let mut pairs: Vec<_> = form_urlencoded::parse(query.as_bytes())
+            .map(|(key, value)| {
+                let (key, index) = split_array_suffix(key);
+                has_index |= index.is_some();
+                (key, index, value)
+            })
+            .collect();
+
+        if has_index {
+            // Only the order of the values of a given key matters.
+            pairs.sort_by(|(a_key, a_index, _), (b_key, b_index, _)| {
+                (a_key, a_index).cmp(&(b_key, b_index))
+            });
+        }
+
+        let mut serializer = form_urlencoded::Serializer::new(String::new());
+        for (key, _, value) in &pairs {
+            serializer.append_pair(key, value);
+        }
+
+        Cow::Owned(serializer.finish())
+    }
+
+    /// Remove the `[]` or `[<index>]` suffix of the given key, if any.
+    fn split_array_suffix(
+        key: std::borrow::Cow<'_, str>,
+    ) -> (std::borrow::Cow<'_, str>, Option<usize>) {
+        let Some(inner) = key.strip_suffix(']') else {
+            return (key, None);
+        };
+        let Some((name, index)) = inner.rsplit_once('[') else {
+            return (key, None);
+        };
+
+        if name.is_empty() || name.contains(['[', ']']) {
+            return (key, None);
+        }
+
+        if index.is_empty() {
+            (name.to_owned().into(), None)
+        } else if let Ok(index) = index.parse() {
+            (name.to_owned().into(), Some(index))
+        } else {
+            (key, None)
+        }
+    }
+}
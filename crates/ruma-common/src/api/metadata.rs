@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     cmp::Ordering,
     fmt::{self, Display, Write},
     str::FromStr,
@@ -9,7 +10,7 @@ use http::{
     header::{self, HeaderName, HeaderValue},
     Method,
 };
-use percent_encoding::utf8_percent_encode;
+use percent_encoding::{percent_decode_str, utf8_percent_encode};
 use tracing::warn;
 
 use super::{
@@ -31,11 +32,24 @@ pub struct Metadata {
     /// What authentication scheme the server uses for this endpoint.
     pub authentication: AuthScheme,
 
+    /// Whether guest accounts are allowed to use this endpoint.
+    pub guest_access: bool,
+
     /// All info pertaining to an endpoint's (historic) paths, deprecation version, and removal.
     pub history: VersionHistory,
 }
 
 impl Metadata {
+    /// Whether guest accounts are allowed to use this endpoint.
+    ///
+    /// This is always `true` for endpoints that don't require an access token. Servers should
+    /// reject requests from guests to endpoints where this is `false` with an
+    /// `M_GUEST_ACCESS_FORBIDDEN` error.
+    pub fn guest_access_allowed(&self) -> bool {
+        self.guest_access
+            || matches!(self.authentication, AuthScheme::None | AuthScheme::AccessTokenOptional)
+    }
+
     /// Returns an empty request body for this Matrix request.
     ///
     /// For `GET` requests, it returns an entirely empty buffer, for others it returns an empty JSON
@@ -88,6 +102,32 @@ impl Metadata {
         })
     }
 
+    /// Match the given request path against the paths of this endpoint.
+    ///
+    /// The path must not contain the query string. If it matches one of the paths of this
+    /// endpoint, returns the percent-decoded path arguments in the order in which they appear in
+    /// the path, for use with [`IncomingRequest::try_from_http_request`].
+    ///
+    /// [`IncomingRequest::try_from_http_request`]: super::IncomingRequest::try_from_http_request
+    pub fn match_path<'a>(&self, path: &'a str) -> Option<Vec<Cow<'a, str>>> {
+        self.history.all_paths().find_map(|canon| {
+            let mut path_args = Vec::new();
+            let mut segments = path.split('/');
+
+            for canon_segment in canon.split('/') {
+                let segment = segments.next()?;
+
+                if canon_segment.starts_with(':') {
+                    path_args.push(percent_decode_str(segment).decode_utf8().ok()?);
+                } else if segment != canon_segment {
+                    return None;
+                }
+            }
+
+            segments.next().is_none().then_some(path_args)
+        })
+    }
+
     /// Generate the endpoint URL for this endpoint.
     pub fn make_endpoint_url(
         &self,
@@ -763,6 +803,7 @@ mod tests {
             method: Method::GET,
             rate_limited: false,
             authentication: AuthScheme::None,
+            guest_access: false,
             history: VersionHistory {
                 unstable_paths: &[],
                 stable_paths,
@@ -872,4 +913,46 @@ mod tests {
 
         assert_eq!(LIT, V1_0);
     }
+
+    #[test]
+    fn match_path() {
+        let meta = stable_only_metadata(&[(V1_0, "/s/:x/t/:y")]);
+
+        assert_eq!(meta.match_path("/s/a/t/b").unwrap(), ["a", "b"]);
+        assert_eq!(meta.match_path("/s/%40a%3Ab/t/b%2Fc").unwrap(), ["@a:b", "b/c"]);
+        assert_eq!(meta.match_path("/s/a/u/b"), None);
+        assert_eq!(meta.match_path("/s/a/t"), None);
+        assert_eq!(meta.match_path("/s/a/t/b/c"), None);
+    }
+
+    #[test]
+    fn guest_access() {
+        const DEFAULT: Metadata = crate::metadata! {
+            method: GET,
+            rate_limited: false,
+            authentication: AccessToken,
+            history: {
+                1.0 => "/s",
+            }
+        };
+        let default = DEFAULT;
+        assert!(!default.guest_access);
+        assert!(!default.guest_access_allowed());
+
+        const GUEST: Metadata = crate::metadata! {
+            method: GET,
+            rate_limited: false,
+            authentication: AccessToken,
+            guest_access: true,
+            history: {
+                1.0 => "/s",
+            }
+        };
+        let guest = GUEST;
+        assert!(guest.guest_access_allowed());
+
+        let unauthenticated = stable_only_metadata(&[(V1_0, "/s")]);
+        assert!(!unauthenticated.guest_access);
+        assert!(unauthenticated.guest_access_allowed());
+    }
 }
@@ -136,7 +136,19 @@ impl Filter {
 
     /// Returns `true` if the filter is empty.
     pub fn is_empty(&self) -> bool {
-        self.generic_search_term.is_none()
+        self.generic_search_term.is_none() && self.room_types.is_empty()
+    }
+
+    /// Whether a room with the given type is allowed by the `room_types` field.
+    ///
+    /// `room_type` should be `None` for rooms that don't have a `type` in their `m.room.create`
+    /// event.
+    pub fn matches_room_type(&self, room_type: Option<&RoomType>) -> bool {
+        self.room_types.is_empty()
+            || self
+                .room_types
+                .iter()
+                .any(|filter| filter.as_str() == room_type.map(RoomType::as_str))
     }
 }
 
@@ -310,6 +322,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn query_string_network() {
+        assert_eq!(
+            serde_html_form::to_string(RoomNetwork::All).unwrap(),
+            "include_all_networks=true"
+        );
+        assert_eq!(
+            serde_html_form::from_str::<RoomNetwork>("include_all_networks=true").unwrap(),
+            RoomNetwork::All
+        );
+        assert_eq!(
+            serde_html_form::from_str::<RoomNetwork>("include_all_networks=false").unwrap(),
+            RoomNetwork::Matrix
+        );
+        assert_eq!(
+            serde_html_form::from_str::<RoomNetwork>("third_party_instance_id=freenode").unwrap(),
+            RoomNetwork::ThirdParty("freenode".into())
+        );
+        serde_html_form::from_str::<RoomNetwork>(
+            "include_all_networks=true&third_party_instance_id=freenode",
+        )
+        .unwrap_err();
+    }
+
     #[test]
     fn serialize_filter_empty() {
         let filter = Filter::default();
@@ -339,6 +375,30 @@ mod tests {
         assert_eq!(to_json_value(filter).unwrap(), json);
     }
 
+    #[test]
+    fn filter_room_types_only_is_not_empty() {
+        let mut filter = Filter::new();
+        assert!(filter.is_empty());
+
+        filter.room_types = vec![RoomTypeFilter::Space];
+        assert!(!filter.is_empty());
+    }
+
+    #[test]
+    fn filter_matches_room_type() {
+        let mut filter = Filter::new();
+        assert!(filter.matches_room_type(None));
+        assert!(filter.matches_room_type(Some(&RoomType::Space)));
+
+        filter.room_types = vec![RoomTypeFilter::Space];
+        assert!(!filter.matches_room_type(None));
+        assert!(filter.matches_room_type(Some(&RoomType::Space)));
+
+        filter.room_types = vec![RoomTypeFilter::Default];
+        assert!(filter.matches_room_type(None));
+        assert!(!filter.matches_room_type(Some(&RoomType::Space)));
+    }
+
     #[test]
     fn deserialize_filter_room_types() {
         let json = json!({ "room_types": [null, "m.space", "custom_type"] });
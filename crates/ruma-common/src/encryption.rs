@@ -172,3 +172,40 @@ pub enum KeyUsage {
     #[doc(hidden)]
     _Custom(PrivOwnedStr),
 }
+
+/// The trust level of a device.
+///
+/// The Matrix specification doesn't define how clients should store their trust decisions. This
+/// type provides a common vocabulary for them, so crates building on Ruma can share trust
+/// information and persist it in the same format.
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
+#[derive(Clone, PartialEq, Eq, StringEnum)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+#[ruma_enum(rename_all = "snake_case")]
+pub enum DeviceTrust {
+    /// The device was verified directly by the current user, for example with an interactive
+    /// key verification.
+    Verified,
+
+    /// The device is signed by the self-signing key of its owner, and the owner's master key is
+    /// trusted.
+    CrossSigned,
+
+    /// The device was neither verified nor cross-signed.
+    Unverified,
+
+    /// The device was explicitly marked as untrusted by the current user.
+    ///
+    /// Room keys should not be shared with such a device.
+    Blacklisted,
+
+    #[doc(hidden)]
+    _Custom(PrivOwnedStr),
+}
+
+impl DeviceTrust {
+    /// Whether the device can be trusted, i.e. it was either verified or cross-signed.
+    pub fn is_trusted(&self) -> bool {
+        matches!(self, Self::Verified | Self::CrossSigned)
+    }
+}
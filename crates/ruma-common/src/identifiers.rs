@@ -15,7 +15,8 @@ use serde::de::{self, Deserializer, Unexpected};
 pub use self::{
     client_secret::{ClientSecret, OwnedClientSecret},
     crypto_algorithms::{
-        DeviceKeyAlgorithm, EventEncryptionAlgorithm, KeyDerivationAlgorithm, SigningKeyAlgorithm,
+        DeviceKeyAlgorithm, EventEncryptionAlgorithm, KeyBackupAlgorithm, KeyDerivationAlgorithm,
+        SecretEncryptionAlgorithm, SigningKeyAlgorithm,
     },
     device_id::{DeviceId, OwnedDeviceId},
     device_key_id::{DeviceKeyId, OwnedDeviceKeyId},
@@ -106,8 +107,8 @@ macro_rules! owned_device_id {
 #[doc(hidden)]
 pub mod __private_macros {
     pub use ruma_macros::{
-        device_key_id, event_id, mxc_uri, room_alias_id, room_id, room_version_id, server_name,
-        server_signing_key_id, user_id,
+        device_key_id, event_id, mxc_uri, room_alias_id, room_id, room_or_alias_id,
+        room_version_id, server_name, server_signing_key_id, user_id,
     };
 }
 
@@ -175,6 +176,22 @@ macro_rules! owned_room_id {
     };
 }
 
+/// Compile-time checked [`RoomOrAliasId`] construction.
+#[macro_export]
+macro_rules! room_or_alias_id {
+    ($s:literal) => {
+        $crate::__private_macros::room_or_alias_id!($crate, $s)
+    };
+}
+
+/// Compile-time checked [`OwnedRoomOrAliasId`] construction.
+#[macro_export]
+macro_rules! owned_room_or_alias_id {
+    ($s:literal) => {
+        $crate::room_or_alias_id!($s).to_owned()
+    };
+}
+
 /// Compile-time checked [`RoomVersionId`] construction.
 #[macro_export]
 macro_rules! room_version_id {
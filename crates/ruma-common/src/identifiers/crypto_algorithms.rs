@@ -66,6 +66,32 @@ pub enum KeyDerivationAlgorithm {
     _Custom(PrivOwnedStr),
 }
 
+/// An algorithm used to encrypt the room keys in a key backup.
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, StringEnum)]
+#[non_exhaustive]
+pub enum KeyBackupAlgorithm {
+    /// Curve25519 ECDH, AES-256 and HMAC-SHA-256 for Megolm sessions.
+    #[ruma_enum(rename = "m.megolm_backup.v1.curve25519-aes-sha2")]
+    MegolmBackupV1Curve25519AesSha2,
+
+    #[doc(hidden)]
+    _Custom(PrivOwnedStr),
+}
+
+/// An algorithm used to encrypt secrets in the secret storage.
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, StringEnum)]
+#[non_exhaustive]
+pub enum SecretEncryptionAlgorithm {
+    /// AES-CTR-256 and HMAC-SHA-256.
+    #[ruma_enum(rename = "m.secret_storage.v1.aes-hmac-sha2")]
+    SecretStorageV1AesHmacSha2,
+
+    #[doc(hidden)]
+    _Custom(PrivOwnedStr),
+}
+
 #[cfg(test)]
 mod tests {
     use super::{DeviceKeyAlgorithm, SigningKeyAlgorithm};
@@ -109,4 +135,30 @@ mod tests {
 
         serde_json_eq(KeyDerivationAlgorithm::Pbkfd2, json!("m.pbkdf2"));
     }
+
+    #[test]
+    fn key_backup_algorithm_serde() {
+        use serde_json::json;
+
+        use super::KeyBackupAlgorithm;
+        use crate::serde::test::serde_json_eq;
+
+        serde_json_eq(
+            KeyBackupAlgorithm::MegolmBackupV1Curve25519AesSha2,
+            json!("m.megolm_backup.v1.curve25519-aes-sha2"),
+        );
+    }
+
+    #[test]
+    fn secret_encryption_algorithm_serde() {
+        use serde_json::json;
+
+        use super::SecretEncryptionAlgorithm;
+        use crate::serde::test::serde_json_eq;
+
+        serde_json_eq(
+            SecretEncryptionAlgorithm::SecretStorageV1AesHmacSha2,
+            json!("m.secret_storage.v1.aes-hmac-sha2"),
+        );
+    }
 }
@@ -68,4 +68,10 @@ mod tests {
         let device_id: OwnedDeviceId = box_str.into();
         assert_eq!(device_id.as_str(), "ijklmnop");
     }
+
+    #[test]
+    fn deserialize_borrowed_device_id() {
+        let device_id = serde_json::from_str::<&DeviceId>(r#""ABCDEFGH""#).unwrap();
+        assert_eq!(device_id.as_str(), "ABCDEFGH");
+    }
 }
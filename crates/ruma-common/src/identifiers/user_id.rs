@@ -284,6 +284,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_borrowed_user_id() {
+        let json = r#""@carl:example.com""#;
+        let user_id =
+            serde_json::from_str::<&UserId>(json).expect("Failed to convert JSON to UserId");
+        assert_eq!(user_id, "@carl:example.com");
+
+        serde_json::from_str::<&UserId>(r#""carl:example.com""#).unwrap_err();
+    }
+
     #[test]
     fn valid_user_id_with_explicit_standard_port() {
         assert_eq!(
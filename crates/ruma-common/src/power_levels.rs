@@ -47,3 +47,12 @@ impl Default for NotificationPowerLevels {
 pub fn default_power_level() -> Int {
     int!(50)
 }
+
+/// The power level of the creator of a room, when the room doesn't have an
+/// `m.room.power_levels` event.
+///
+/// This is also the power level given to the creator by the initial `m.room.power_levels` event
+/// of a room.
+pub fn default_creator_power_level() -> Int {
+    int!(100)
+}
@@ -25,7 +25,7 @@ use tracing::instrument;
 
 use crate::{
     serde::{Raw, StringEnum},
-    OwnedRoomId, OwnedUserId, PrivOwnedStr,
+    OwnedRoomId, OwnedUserId, PrivOwnedStr, RoomId,
 };
 
 mod action;
@@ -357,6 +357,75 @@ impl Ruleset {
 
         Ok(())
     }
+
+    /// Get the notification mode that was set for the room with the given ID.
+    ///
+    /// Returns `None` if there are no user-defined rules for this room, in which case the
+    /// server-default rules apply.
+    pub fn room_notification_mode(&self, room_id: &RoomId) -> Option<RoomNotificationMode> {
+        let is_mute_rule = |rule: &ConditionalPushRule| {
+            rule.enabled
+                && !rule.actions.iter().any(Action::should_notify)
+                && matches!(
+                    rule.conditions.as_slice(),
+                    [PushCondition::EventMatch { key, pattern }]
+                        if key == "room_id" && pattern == room_id.as_str()
+                )
+        };
+
+        if self.override_.get(room_id.as_str()).is_some_and(is_mute_rule) {
+            return Some(RoomNotificationMode::Mute);
+        }
+
+        self.room.get(room_id.as_str()).filter(|rule| rule.enabled).map(|rule| {
+            if rule.actions.iter().any(Action::should_notify) {
+                RoomNotificationMode::AllMessages
+            } else {
+                RoomNotificationMode::MentionsAndKeywordsOnly
+            }
+        })
+    }
+
+    /// Set the notification mode of the room with the given ID.
+    ///
+    /// This replaces the user-defined room and override rules for this room with the rule
+    /// corresponding to the given mode.
+    ///
+    /// Returns an error if the room ID can't be used as a rule ID.
+    pub fn set_room_notification_mode(
+        &mut self,
+        room_id: &RoomId,
+        mode: RoomNotificationMode,
+    ) -> Result<(), InsertPushRuleError> {
+        let rule = match mode {
+            RoomNotificationMode::AllMessages => {
+                NewPushRule::Room(NewSimplePushRule::new(room_id.to_owned(), vec![Action::Notify]))
+            }
+            RoomNotificationMode::MentionsAndKeywordsOnly => {
+                NewPushRule::Room(NewSimplePushRule::new(room_id.to_owned(), vec![]))
+            }
+            RoomNotificationMode::Mute => NewPushRule::Override(NewConditionalPushRule::new(
+                room_id.as_str().to_owned(),
+                vec![room_id_condition(room_id)],
+                vec![],
+            )),
+        };
+
+        self.reset_room_notification_mode(room_id);
+        self.insert(rule, None, None)
+    }
+
+    /// Remove the user-defined room and override rules for the room with the given ID, so that
+    /// the server-default rules apply.
+    pub fn reset_room_notification_mode(&mut self, room_id: &RoomId) {
+        self.override_.shift_remove(room_id.as_str());
+        self.room.shift_remove(room_id.as_str());
+    }
+}
+
+/// The condition of an override rule that matches all the events in the given room.
+fn room_id_condition(room_id: &RoomId) -> PushCondition {
+    PushCondition::EventMatch { key: "room_id".to_owned(), pattern: room_id.as_str().to_owned() }
 }
 
 /// A push rule is a single rule that states under what conditions an event should be passed onto a
@@ -764,6 +833,28 @@ pub enum RuleKind {
     _Custom(PrivOwnedStr),
 }
 
+/// The notification mode of a room, as presented to users.
+///
+/// See [`Ruleset::room_notification_mode()`] for how the modes map to push rules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum RoomNotificationMode {
+    /// Notify for all messages.
+    ///
+    /// This uses a room rule that notifies.
+    AllMessages,
+
+    /// Only notify for mentions and keywords.
+    ///
+    /// This uses a room rule without actions.
+    MentionsAndKeywordsOnly,
+
+    /// Never notify.
+    ///
+    /// This uses an override rule without actions that matches the ID of the room.
+    Mute,
+}
+
 /// A push rule to update or create.
 #[derive(Clone, Debug)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
@@ -1002,6 +1093,7 @@ mod tests {
         owned_room_id, owned_user_id,
         power_levels::NotificationPowerLevels,
         push::{PredefinedContentRuleId, PredefinedOverrideRuleId},
+        room_id,
         serde::Raw,
         user_id,
     };
@@ -1166,7 +1258,7 @@ mod tests {
                 Action::SetTweak(Tweak::Sound("default".into())),
                 Action::SetTweak(Tweak::Custom {
                     name: "dance".into(),
-                    value: RawJsonValue::from_string("true".into()).unwrap(),
+                    value: Some(RawJsonValue::from_string("true".into()).unwrap()),
                 }),
             ],
             default: true,
@@ -1869,4 +1961,56 @@ mod tests {
             PredefinedOverrideRuleId::InviteForMe.as_ref()
         );
     }
+
+    #[test]
+    fn room_notification_mode() {
+        use super::RoomNotificationMode;
+
+        let room_id = room_id!("!far_west:server.name");
+        let context = &PushConditionRoomCtx {
+            room_id: room_id.to_owned(),
+            member_count: uint!(100),
+            user_id: owned_user_id!("@jj:server.name"),
+            user_display_name: "Jolly Jumper".into(),
+            power_levels: Some(power_levels()),
+            #[cfg(feature = "unstable-msc3931")]
+            supported_features: Default::default(),
+        };
+        let message = serde_json::from_str::<Raw<JsonValue>>(
+            r#"{
+                "type": "m.room.message",
+                "room_id": "!far_west:server.name",
+                "sender": "@rantanplan:server.name",
+                "content": {
+                    "body": "Woof!"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let mut set = Ruleset::server_default(user_id!("@jj:server.name"));
+        assert_eq!(set.room_notification_mode(room_id), None);
+
+        set.set_room_notification_mode(room_id, RoomNotificationMode::MentionsAndKeywordsOnly)
+            .unwrap();
+        assert_eq!(
+            set.room_notification_mode(room_id),
+            Some(RoomNotificationMode::MentionsAndKeywordsOnly)
+        );
+        assert_matches!(set.get_actions(&message, context), []);
+
+        set.set_room_notification_mode(room_id, RoomNotificationMode::Mute).unwrap();
+        assert_eq!(set.room_notification_mode(room_id), Some(RoomNotificationMode::Mute));
+        assert!(set.room.is_empty());
+        assert_matches!(set.get_actions(&message, context), []);
+
+        set.set_room_notification_mode(room_id, RoomNotificationMode::AllMessages).unwrap();
+        assert_eq!(set.room_notification_mode(room_id), Some(RoomNotificationMode::AllMessages));
+        assert!(set.override_.get(room_id.as_str()).is_none());
+        assert_matches!(set.get_actions(&message, context), [Action::Notify]);
+
+        set.reset_room_notification_mode(room_id);
+        assert_eq!(set.room_notification_mode(room_id), None);
+        assert!(set.room.is_empty());
+    }
 }
@@ -42,6 +42,13 @@ impl Action {
 }
 
 /// The `set_tweak` action.
+///
+/// If a `highlight` tweak is given with no value, its value is defined to be `true`. Known tweaks
+/// with a missing value or a value of an unexpected type are deserialized as [`Tweak::Custom`].
+///
+/// A `set_tweak` action with other fields than `set_tweak` and `value` can't be represented by
+/// this type, it is deserialized as an unknown [`Action`] instead, so that it is not altered
+/// during a round-trip.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 #[serde(from = "tweak_serde::Tweak", into = "tweak_serde::Tweak")]
@@ -66,8 +73,8 @@ pub enum Tweak {
         /// The name of the custom tweak (`set_tweak` field)
         name: String,
 
-        /// The value of the custom tweak
-        value: Box<RawJsonValue>,
+        /// The value of the custom tweak, if any
+        value: Option<Box<RawJsonValue>>,
     },
 }
 
@@ -85,7 +92,10 @@ impl<'de> Deserialize<'de> for Action {
                 _ => Ok(Action::_Custom(custom)),
             },
             CustomAction::Object(o) => {
-                if o.get("set_tweak").is_some() {
+                let is_tweak = o.contains_key("set_tweak")
+                    && o.keys().all(|key| matches!(key.as_str(), "set_tweak" | "value"));
+
+                if is_tweak {
                     Ok(Action::SetTweak(from_raw_json_value(&json)?))
                 } else {
                     Ok(Action::_Custom(custom))
@@ -122,11 +132,13 @@ pub enum CustomAction {
 }
 
 mod tweak_serde {
-    use serde::{Deserialize, Serialize};
+    use serde::{de, Deserialize, Serialize};
     use serde_json::value::RawValue as RawJsonValue;
 
+    use crate::serde::from_raw_json_value;
+
     /// Values for the `set_tweak` action.
-    #[derive(Clone, Deserialize, Serialize)]
+    #[derive(Clone, Serialize)]
     #[serde(untagged)]
     pub(crate) enum Tweak {
         Sound(SoundTweak),
@@ -134,23 +146,55 @@ mod tweak_serde {
         Custom {
             #[serde(rename = "set_tweak")]
             name: String,
-            value: Box<RawJsonValue>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            value: Option<Box<RawJsonValue>>,
         },
     }
 
-    #[derive(Clone, PartialEq, Deserialize, Serialize)]
+    impl<'de> Deserialize<'de> for Tweak {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            #[derive(Deserialize)]
+            struct TweakDeHelper {
+                set_tweak: String,
+                value: Option<Box<RawJsonValue>>,
+            }
+
+            let TweakDeHelper { set_tweak: name, value } =
+                TweakDeHelper::deserialize(deserializer)?;
+
+            // Known tweaks with an invalid value are kept as custom tweaks, to avoid losing data.
+            match (name.as_str(), value) {
+                ("sound", Some(value)) => {
+                    match from_raw_json_value::<_, de::value::Error>(&value) {
+                        Ok(value) => Ok(Self::Sound(SoundTweak { value })),
+                        Err(_) => Ok(Self::Custom { name, value: Some(value) }),
+                    }
+                }
+                ("highlight", None) => Ok(Self::Highlight(HighlightTweak { value: true })),
+                ("highlight", Some(value)) => {
+                    match from_raw_json_value::<_, de::value::Error>(&value) {
+                        Ok(value) => Ok(Self::Highlight(HighlightTweak { value })),
+                        Err(_) => Ok(Self::Custom { name, value: Some(value) }),
+                    }
+                }
+                (_, value) => Ok(Self::Custom { name, value }),
+            }
+        }
+    }
+
+    #[derive(Clone, PartialEq, Serialize)]
     #[serde(tag = "set_tweak", rename = "sound")]
     pub(crate) struct SoundTweak {
         value: String,
     }
 
-    #[derive(Clone, PartialEq, Deserialize, Serialize)]
+    #[derive(Clone, PartialEq, Serialize)]
     #[serde(tag = "set_tweak", rename = "highlight")]
     pub(crate) struct HighlightTweak {
-        #[serde(
-            default = "crate::serde::default_true",
-            skip_serializing_if = "crate::serde::is_true"
-        )]
+        #[serde(skip_serializing_if = "crate::serde::is_true")]
         value: bool,
     }
 
@@ -182,7 +226,10 @@ mod tweak_serde {
 #[cfg(test)]
 mod tests {
     use assert_matches2::assert_matches;
-    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+    use serde_json::{
+        from_value as from_json_value, json, to_value as to_json_value,
+        value::to_raw_value as to_raw_json_value,
+    };
 
     use super::{Action, Tweak};
 
@@ -249,4 +296,63 @@ mod tests {
             Ok(Action::SetTweak(Tweak::Highlight(true)))
         );
     }
+
+    #[test]
+    fn serialize_tweak_custom() {
+        assert_eq!(
+            to_json_value(Action::SetTweak(Tweak::Custom {
+                name: "dev.local.tweak".into(),
+                value: Some(to_raw_json_value(&json!({ "a": "b" })).unwrap()),
+            }))
+            .unwrap(),
+            json!({ "set_tweak": "dev.local.tweak", "value": { "a": "b" } })
+        );
+    }
+
+    #[test]
+    fn tweak_without_value_roundtrip() {
+        let json = json!({ "set_tweak": "dev.local.tweak" });
+
+        let action = from_json_value::<Action>(json.clone()).unwrap();
+        assert_matches!(&action, Action::SetTweak(Tweak::Custom { name, value: None }));
+        assert_eq!(name, "dev.local.tweak");
+        assert_eq!(to_json_value(action).unwrap(), json);
+
+        let json = json!({ "set_tweak": "sound" });
+
+        let action = from_json_value::<Action>(json.clone()).unwrap();
+        assert_matches!(&action, Action::SetTweak(Tweak::Custom { name, value: None }));
+        assert_eq!(name, "sound");
+        assert_eq!(to_json_value(action).unwrap(), json);
+    }
+
+    #[test]
+    fn deserialize_tweak_with_invalid_value() {
+        assert_matches!(
+            from_json_value::<Action>(json!({ "set_tweak": "sound", "value": true })),
+            Ok(Action::SetTweak(Tweak::Custom { name, value: Some(value) }))
+        );
+        assert_eq!(name, "sound");
+        assert_eq!(value.get(), "true");
+
+        assert_matches!(
+            from_json_value::<Action>(json!({ "set_tweak": "highlight", "value": "yes" })),
+            Ok(Action::SetTweak(Tweak::Custom { name, value: Some(value) }))
+        );
+        assert_eq!(name, "highlight");
+        assert_eq!(value.get(), r#""yes""#);
+    }
+
+    #[test]
+    fn tweak_with_unknown_fields_roundtrip() {
+        let json = json!({
+            "set_tweak": "highlight",
+            "value": false,
+            "dev.local.field": "foo",
+        });
+
+        let action = from_json_value::<Action>(json.clone()).unwrap();
+        assert_matches!(&action, Action::_Custom(_));
+        assert_eq!(to_json_value(action).unwrap(), json);
+    }
 }
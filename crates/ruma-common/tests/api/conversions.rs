@@ -80,6 +80,44 @@ fn request_serde() {
     assert_eq!(req.user, req2.user);
 }
 
+#[test]
+fn request_serde_matching_path() {
+    let req = Request {
+        hello: "hi".to_owned(),
+        world: "test".to_owned(),
+        q1: "query_param_special_chars %/&@!".to_owned(),
+        q2: 55,
+        bar: "bar Val".to_owned(),
+        user: owned_user_id!("@bazme:ruma.io"),
+    };
+
+    let http_req = req
+        .clone()
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    let req2 = Request::try_from_matching_http_request(http_req).unwrap().unwrap();
+
+    assert_eq!(req.hello, req2.hello);
+    assert_eq!(req.world, req2.world);
+    assert_eq!(req.q1, req2.q1);
+    assert_eq!(req.q2, req2.q2);
+    assert_eq!(req.bar, req2.bar);
+    assert_eq!(req.user, req2.user);
+
+    let other_path =
+        http::Request::post("https://homeserver.tld/_matrix/foo/bar").body(b"{}".to_vec()).unwrap();
+    assert!(Request::try_from_matching_http_request(other_path).unwrap().is_none());
+
+    let other_method = http::Request::get("https://homeserver.tld/_matrix/foo/bar/@bazme:ruma.io")
+        .body(Vec::new())
+        .unwrap();
+    assert!(Request::try_from_matching_http_request(other_method).unwrap().is_none());
+}
+
 #[test]
 fn invalid_uri_should_not_panic() {
     let req = Request {
@@ -198,3 +236,68 @@ mod without_query {
         assert_eq!(query, "user_id=%40_virtual_%3Aruma.io");
     }
 }
+
+mod bracketed_query {
+    use ruma_common::{
+        api::{request, response, IncomingRequest as _, Metadata},
+        metadata,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: None,
+        history: {
+            unstable => "/_matrix/foo",
+        }
+    };
+
+    /// Request type for the `my_endpoint` endpoint.
+    #[request]
+    pub struct Request {
+        #[ruma_api(query)]
+        #[serde(default)]
+        pub server: Vec<String>,
+
+        #[ruma_api(query)]
+        pub limit: Option<u32>,
+    }
+
+    /// Response type for the `my_endpoint` endpoint.
+    #[response]
+    pub struct Response {}
+
+    fn parse(query: &str) -> Request {
+        let http_req = http::Request::builder()
+            .method(http::Method::GET)
+            .uri(format!("https://homeserver.tld/_matrix/foo?{query}"))
+            .body(Vec::<u8>::new())
+            .unwrap();
+        Request::try_from_http_request::<_, &str>(http_req, &[]).unwrap()
+    }
+
+    #[test]
+    fn repeated_keys() {
+        let req = parse("server=a.org&limit=5&server=b.org");
+        assert_eq!(req.server, ["a.org", "b.org"]);
+        assert_eq!(req.limit, Some(5));
+    }
+
+    #[test]
+    fn bracketed_keys() {
+        let req = parse("server[]=a.org&server%5B%5D=b.org&limit=5");
+        assert_eq!(req.server, ["a.org", "b.org"]);
+        assert_eq!(req.limit, Some(5));
+
+        let req = parse("server[1]=b.org&limit=5&server[0]=a.org");
+        assert_eq!(req.server, ["a.org", "b.org"]);
+        assert_eq!(req.limit, Some(5));
+    }
+
+    #[test]
+    fn nested_keys_are_ignored() {
+        let req = parse("filter[limit]=5&server=a.org");
+        assert_eq!(req.server, ["a.org"]);
+        assert_eq!(req.limit, None);
+    }
+}
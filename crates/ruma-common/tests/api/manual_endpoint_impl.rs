@@ -25,6 +25,7 @@ const METADATA: Metadata = Metadata {
     method: Method::PUT,
     rate_limited: false,
     authentication: AuthScheme::None,
+    guest_access: false,
     history: VersionHistory::new(
         &["/_matrix/client/unstable/directory/room/:room_alias"],
         &[
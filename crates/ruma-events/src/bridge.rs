@@ -0,0 +1,71 @@
+//! Types for the `uk.half-shot.bridge` state event, the unstable version of `m.bridge`
+//! ([MSC2346]).
+//!
+//! [MSC2346]: https://github.com/matrix-org/matrix-spec-proposals/pull/2346
+
+use ruma_common::{OwnedMxcUri, OwnedUserId};
+use ruma_macros::EventContent;
+use serde::{Deserialize, Serialize};
+
+/// The content of a bridge state event.
+///
+/// Bridges set this event in the rooms they bridge to describe the remote channel that the room
+/// is connected to. A room can be bridged to several channels, so the `state_key` must be unique
+/// for every bridged channel. It is recommended to use
+/// `<bridge_identifier>/<protocol.id>/<network.id>/<channel.id>`, with `/` in the IDs being
+/// escaped as `%2F`.
+#[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+#[ruma_event(type = "uk.half-shot.bridge", alias = "m.bridge", kind = State, state_key_type = String)]
+pub struct BridgeEventContent {
+    /// The ID of the bridge bot user.
+    pub bridgebot: OwnedUserId,
+
+    /// The ID of the user that provisioned the bridge, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<OwnedUserId>,
+
+    /// The protocol that is bridged.
+    pub protocol: BridgeInfo,
+
+    /// The network of the protocol that is bridged, if the protocol has several networks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<BridgeInfo>,
+
+    /// The remote channel that is bridged.
+    pub channel: BridgeInfo,
+}
+
+impl BridgeEventContent {
+    /// Creates a new `BridgeEventContent` with the given bridge bot user ID, protocol and channel.
+    pub fn new(bridgebot: OwnedUserId, protocol: BridgeInfo, channel: BridgeInfo) -> Self {
+        Self { bridgebot, creator: None, protocol, network: None, channel }
+    }
+}
+
+/// Information about a protocol, network or channel of a [`BridgeEventContent`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct BridgeInfo {
+    /// An identifier that is unique among all the bridged entities of the same kind.
+    pub id: String,
+
+    /// A human-readable name to display.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub displayname: Option<String>,
+
+    /// The URL of an avatar to display.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<OwnedMxcUri>,
+
+    /// A URL to view the entity outside of Matrix.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_url: Option<String>,
+}
+
+impl BridgeInfo {
+    /// Creates a new `BridgeInfo` with the given ID.
+    pub fn new(id: String) -> Self {
+        Self { id, displayname: None, avatar_url: None, external_url: None }
+    }
+}
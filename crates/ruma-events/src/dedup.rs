@@ -0,0 +1,101 @@
+use ruma_common::{
+    EventId, OwnedEventId, OwnedRoomId, OwnedTransactionId, OwnedUserId, RoomId, TransactionId,
+    UserId,
+};
+
+use crate::{AnySyncTimelineEvent, AnyTimelineEvent};
+
+/// A key for clients to detect duplicates of a timeline event.
+///
+/// An event can be identified in two ways:
+///
+/// * By its transaction ID, before the server has assigned an event ID. Clients must use a unique
+///   transaction ID for each event they send, and reuse it when retrying to send the same event.
+/// * By its event ID, once it has been created by the server.
+///
+/// The `unsigned.transaction_id` of an event is only set when it is sent to the device that sent
+/// it, so clients can use it to match the event received via sync with its local echo.
+///
+/// Transaction IDs are scoped to the device that sent the event, which is not part of this key.
+/// It is only meant to be used by a single device to deduplicate its own events, and is not
+/// suitable for servers, which must deduplicate requests per device.
+///
+/// Two keys refer to the same event if they have the same room ID and sender, and either the same
+/// transaction ID or the same event ID, so keys must be compared with
+/// [`DedupKey::is_duplicate_of()`]. This type doesn't implement `PartialEq` or `Hash` because this
+/// relation is not transitive.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct DedupKey {
+    /// The ID of the room the event belongs to.
+    pub room_id: OwnedRoomId,
+
+    /// The sender of the event.
+    pub sender: OwnedUserId,
+
+    /// The transaction ID used to send the event, if known.
+    pub transaction_id: Option<OwnedTransactionId>,
+
+    /// The ID of the event, if known.
+    pub event_id: Option<OwnedEventId>,
+}
+
+impl DedupKey {
+    /// Creates a `DedupKey` for an event that is sent with the given transaction ID.
+    pub fn for_transaction(
+        room_id: OwnedRoomId,
+        sender: OwnedUserId,
+        transaction_id: OwnedTransactionId,
+    ) -> Self {
+        Self { room_id, sender, transaction_id: Some(transaction_id), event_id: None }
+    }
+
+    /// Creates a `DedupKey` for the event with the given event ID.
+    pub fn for_event(room_id: OwnedRoomId, sender: OwnedUserId, event_id: OwnedEventId) -> Self {
+        Self { room_id, sender, transaction_id: None, event_id: Some(event_id) }
+    }
+
+    /// Whether this key and the given key refer to the same event.
+    pub fn is_duplicate_of(&self, other: &DedupKey) -> bool {
+        if self.room_id != other.room_id || self.sender != other.sender {
+            return false;
+        }
+
+        let same_transaction = matches!(
+            (&self.transaction_id, &other.transaction_id),
+            (Some(a), Some(b)) if a == b
+        );
+        let same_event = matches!((&self.event_id, &other.event_id), (Some(a), Some(b)) if a == b);
+
+        same_transaction || same_event
+    }
+
+    fn from_parts(
+        room_id: &RoomId,
+        sender: &UserId,
+        transaction_id: Option<&TransactionId>,
+        event_id: &EventId,
+    ) -> Self {
+        Self {
+            room_id: room_id.to_owned(),
+            sender: sender.to_owned(),
+            transaction_id: transaction_id.map(ToOwned::to_owned),
+            event_id: Some(event_id.to_owned()),
+        }
+    }
+}
+
+impl AnyTimelineEvent {
+    /// Returns the key to detect duplicates of this event.
+    pub fn dedup_key(&self) -> DedupKey {
+        DedupKey::from_parts(self.room_id(), self.sender(), self.transaction_id(), self.event_id())
+    }
+}
+
+impl AnySyncTimelineEvent {
+    /// Returns the key to detect duplicates of this event, given the ID of the room it was
+    /// received in.
+    pub fn dedup_key(&self, room_id: &RoomId) -> DedupKey {
+        DedupKey::from_parts(room_id, self.sender(), self.transaction_id(), self.event_id())
+    }
+}
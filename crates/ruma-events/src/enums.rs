@@ -6,7 +6,7 @@ use ruma_macros::{event_enum, EventEnumFromEvent};
 use serde::{de, Deserialize};
 use serde_json::value::RawValue as RawJsonValue;
 
-use super::room::encrypted;
+use super::{room::encrypted, UnsignedRoomRedactionEvent};
 
 event_enum! {
     /// Any global account data event.
@@ -129,6 +129,9 @@ event_enum! {
         "m.room.topic" => super::room::topic,
         "m.space.child" => super::space::child,
         "m.space.parent" => super::space::parent,
+        #[cfg(feature = "unstable-msc2346")]
+        #[ruma_enum(alias = "m.bridge")]
+        "uk.half-shot.bridge" => super::bridge,
         #[cfg(feature = "unstable-msc3489")]
         #[ruma_enum(alias = "m.beacon_info")]
         "org.matrix.msc3672.beacon_info" => super::beacon_info,
@@ -203,6 +206,15 @@ impl AnyTimelineEvent {
 
         /// Returns this event's `transaction_id` from inside `unsigned`, if there is one.
         pub fn transaction_id(&self) -> Option<&TransactionId>;
+
+        /// Whether this event is redacted.
+        pub fn is_redacted(&self) -> bool;
+
+        /// Returns this event's `redacted_because` from inside `unsigned`, if it is redacted.
+        pub fn redacted_because(&self) -> Option<&UnsignedRoomRedactionEvent>;
+
+        /// Returns the `reason` of this event's redaction, if it is redacted and has one.
+        pub fn redaction_reason(&self) -> Option<&str>;
     }
 
     /// Returns this event's `type`.
@@ -240,6 +252,15 @@ impl AnySyncTimelineEvent {
 
         /// Returns this event's `transaction_id` from inside `unsigned`, if there is one.
         pub fn transaction_id(&self) -> Option<&TransactionId>;
+
+        /// Whether this event is redacted.
+        pub fn is_redacted(&self) -> bool;
+
+        /// Returns this event's `redacted_because` from inside `unsigned`, if it is redacted.
+        pub fn redacted_because(&self) -> Option<&UnsignedRoomRedactionEvent>;
+
+        /// Returns the `reason` of this event's redaction, if it is redacted and has one.
+        pub fn redaction_reason(&self) -> Option<&str>;
     }
 
     /// Returns this event's `type`.
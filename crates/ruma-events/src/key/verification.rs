@@ -126,11 +126,61 @@ pub enum VerificationMethod {
     _Custom(PrivOwnedStr),
 }
 
+/// The verification methods that can be used between two devices.
+///
+/// They are computed from the `methods` that each device advertises in its
+/// `m.key.verification.request` or `m.key.verification.ready` event, following the rules of the
+/// [key verification framework].
+///
+/// [key verification framework]: https://spec.matrix.org/latest/client-server-api/#key-verification-framework
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct MutualMethods {
+    /// Whether both devices support Short Authentication String verification.
+    pub sas: bool,
+
+    /// Whether our device can show a QR code for the other device to scan.
+    pub show_qr_code: bool,
+
+    /// Whether our device can scan a QR code shown by the other device.
+    pub scan_qr_code: bool,
+}
+
+impl MutualMethods {
+    /// Compute the verification methods that can be used between our device and the other
+    /// device, given the methods that each of them supports.
+    pub fn new(ours: &[VerificationMethod], theirs: &[VerificationMethod]) -> Self {
+        let both_support =
+            |method: &VerificationMethod| ours.contains(method) && theirs.contains(method);
+        let qr_code = both_support(&VerificationMethod::ReciprocateV1);
+
+        Self {
+            sas: both_support(&VerificationMethod::SasV1),
+            show_qr_code: qr_code
+                && ours.contains(&VerificationMethod::QrCodeShowV1)
+                && theirs.contains(&VerificationMethod::QrCodeScanV1),
+            scan_qr_code: qr_code
+                && ours.contains(&VerificationMethod::QrCodeScanV1)
+                && theirs.contains(&VerificationMethod::QrCodeShowV1),
+        }
+    }
+
+    /// Whether at least one verification method can be used.
+    ///
+    /// If this is `false`, the verification request should be cancelled with the
+    /// `m.unknown_method` code.
+    pub fn any(&self) -> bool {
+        self.sas || self.show_qr_code || self.scan_qr_code
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::{from_value as from_json_value, json};
 
-    use super::{KeyAgreementProtocol, MessageAuthenticationCode};
+    use super::{
+        KeyAgreementProtocol, MessageAuthenticationCode, MutualMethods, VerificationMethod,
+    };
 
     #[test]
     fn serialize_key_agreement() {
@@ -174,4 +224,21 @@ mod tests {
         assert_eq!(serialized, "\"hkdf-hmac-sha256.v2\"");
         assert_eq!(deserialized, MessageAuthenticationCode::HkdfHmacSha256V2);
     }
+
+    #[test]
+    fn mutual_methods() {
+        use VerificationMethod::*;
+
+        let methods = MutualMethods::new(
+            &[SasV1, QrCodeShowV1, QrCodeScanV1, ReciprocateV1],
+            &[SasV1, QrCodeScanV1, ReciprocateV1],
+        );
+        assert_eq!(methods, MutualMethods { sas: true, show_qr_code: true, scan_qr_code: false });
+        assert!(methods.any());
+
+        // QR codes need both devices to support reciprocation.
+        let methods = MutualMethods::new(&[QrCodeShowV1, ReciprocateV1], &[QrCodeScanV1]);
+        assert_eq!(methods, MutualMethods::default());
+        assert!(!methods.any());
+    }
 }
@@ -16,7 +16,7 @@ use super::{
     MessageLikeEventType, MessageLikeUnsigned, PossiblyRedactedStateEventContent, RedactContent,
     RedactedMessageLikeEventContent, RedactedStateEventContent, RedactedUnsigned,
     RedactionDeHelper, RoomAccountDataEventContent, StateEventType, StaticStateEventContent,
-    ToDeviceEventContent,
+    ToDeviceEventContent, UnsignedRoomRedactionEvent,
 };
 
 /// A global account data event.
@@ -623,6 +623,24 @@ macro_rules! impl_possibly_redacted_event {
                 }
             }
 
+            /// Whether this event is redacted.
+            pub fn is_redacted(&self) -> bool {
+                matches!(self, Self::Redacted(_))
+            }
+
+            /// Returns this event's `redacted_because` from inside `unsigned`, if it is redacted.
+            pub fn redacted_because(&self) -> Option<&UnsignedRoomRedactionEvent> {
+                match self {
+                    Self::Original(_) => None,
+                    Self::Redacted(ev) => Some(&ev.unsigned.redacted_because),
+                }
+            }
+
+            /// Returns the `reason` of this event's redaction, if it is redacted and has one.
+            pub fn redaction_reason(&self) -> Option<&str> {
+                self.redacted_because()?.content.reason.as_deref()
+            }
+
             // So the room_id method can be in the same impl block, in rustdoc
             $($extra)*
         }
@@ -773,3 +791,66 @@ impl_sync_from_full!(
     StaticStateEventContent,
     RedactedStateEventContent
 );
+
+macro_rules! impl_redact_original_event {
+    (
+        $ty:ident => $redacted:ident ( $content_trait:ident, $redacted_content_trait:ident )
+        $( where C::Redacted: $trait:ident<StateKey = C::StateKey>, )?
+        { $($field:ident),* }
+    ) => {
+        impl<C> $ty<C>
+        where
+            C: $content_trait + RedactContent,
+            C::Redacted: $redacted_content_trait,
+            $( C::Redacted: $trait<StateKey = C::StateKey>, )?
+        {
+            /// Transform `self` into a redacted form (removing most or all fields) according to the
+            /// spec.
+            ///
+            /// The given redaction event is set as the `redacted_because` of the redacted event's
+            /// `unsigned` field.
+            ///
+            /// A small number of events have room-version specific redaction behavior, so a version
+            /// has to be specified.
+            pub fn redact(
+                self,
+                redacted_because: UnsignedRoomRedactionEvent,
+                version: &RoomVersionId,
+            ) -> $redacted<C::Redacted> {
+                $redacted {
+                    content: self.content.redact(version),
+                    event_id: self.event_id,
+                    sender: self.sender,
+                    origin_server_ts: self.origin_server_ts,
+                    unsigned: RedactedUnsigned::new(redacted_because),
+                    $( $field: self.$field, )*
+                }
+            }
+        }
+    };
+}
+
+impl_redact_original_event!(
+    OriginalMessageLikeEvent => RedactedMessageLikeEvent(
+        MessageLikeEventContent, RedactedMessageLikeEventContent
+    ) { room_id }
+);
+impl_redact_original_event!(
+    OriginalSyncMessageLikeEvent => RedactedSyncMessageLikeEvent(
+        MessageLikeEventContent, RedactedMessageLikeEventContent
+    ) {}
+);
+impl_redact_original_event!(
+    OriginalStateEvent => RedactedStateEvent(StaticStateEventContent, RedactedStateEventContent)
+    where
+        C::Redacted: RedactedStateEventContent<StateKey = C::StateKey>,
+    { room_id, state_key }
+);
+impl_redact_original_event!(
+    OriginalSyncStateEvent => RedactedSyncStateEvent(
+        StaticStateEventContent, RedactedStateEventContent
+    )
+    where
+        C::Redacted: RedactedStateEventContent<StateKey = C::StateKey>,
+    { state_key }
+);
@@ -106,13 +106,20 @@
 
 use std::{collections::BTreeSet, fmt};
 
-use ruma_common::{EventEncryptionAlgorithm, OwnedUserId, RoomVersionId};
-use serde::{de::IgnoredAny, Deserialize, Serialize, Serializer};
+use ruma_common::{
+    EventEncryptionAlgorithm, MilliSecondsSinceUnixEpoch, OwnedUserId, RoomVersionId,
+};
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::value::RawValue as RawJsonValue;
 
 // Needs to be public for trybuild tests
 #[doc(hidden)]
 pub mod _custom;
 mod content;
+mod dedup;
 mod enums;
 mod kinds;
 mod state_key;
@@ -143,6 +150,8 @@ pub mod audio;
 pub mod beacon;
 #[cfg(feature = "unstable-msc3489")]
 pub mod beacon_info;
+#[cfg(feature = "unstable-msc2346")]
+pub mod bridge;
 pub mod call;
 pub mod direct;
 pub mod dummy;
@@ -191,6 +200,7 @@ pub mod voice;
 
 pub use self::{
     content::*,
+    dedup::DedupKey,
     enums::*,
     kinds::*,
     relation::{BundledMessageLikeRelations, BundledStateRelations},
@@ -248,6 +258,40 @@ pub fn serialize_custom_event_error<T, S: Serializer>(_: &T, _: S) -> Result<S::
     ))
 }
 
+/// Helper function for the `origin_server_ts` field of events when it is missing during
+/// deserialization.
+///
+/// With the `compat-optional-origin-server-ts` cargo feature, it defaults to the Unix epoch.
+/// Otherwise, it is a missing field error.
+#[doc(hidden)]
+pub fn origin_server_ts_or_default<E: serde::de::Error>(
+    origin_server_ts: Option<MilliSecondsSinceUnixEpoch>,
+) -> Result<MilliSecondsSinceUnixEpoch, E> {
+    match origin_server_ts {
+        Some(origin_server_ts) => Ok(origin_server_ts),
+        None if cfg!(feature = "compat-optional-origin-server-ts") => {
+            Ok(MilliSecondsSinceUnixEpoch(js_int::UInt::MIN))
+        }
+        None => Err(E::missing_field("origin_server_ts")),
+    }
+}
+
+/// Helper function for deserializing an optional field, falling back to `None` if its value is
+/// invalid.
+///
+/// This is meant for data added by the homeserver that is not essential to the event, so that an
+/// error in it doesn't prevent the whole event from being deserialized.
+pub(crate) fn none_on_invalid<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let Some(json) = Option::<Box<RawJsonValue>>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    Ok(serde_json::from_str(json.get()).ok())
+}
+
 /// Describes whether the event mentions other users or the room.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[non_exhaustive]
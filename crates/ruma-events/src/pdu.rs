@@ -9,8 +9,8 @@ use std::collections::BTreeMap;
 
 use js_int::UInt;
 use ruma_common::{
-    MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedRoomId, OwnedServerName,
-    OwnedServerSigningKeyId, OwnedUserId,
+    serde::StringEnum, MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedRoomId, OwnedServerName,
+    OwnedServerSigningKeyId, OwnedUserId, RoomVersionId,
 };
 use serde::{
     de::{Error as _, IgnoredAny},
@@ -19,6 +19,7 @@ use serde::{
 use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue};
 
 use super::TimelineEventType;
+use crate::PrivOwnedStr;
 
 /// Enum for PDU schemas
 #[derive(Clone, Debug, Serialize)]
@@ -32,6 +33,32 @@ pub enum Pdu {
     RoomV3Pdu(RoomV3Pdu),
 }
 
+impl Pdu {
+    /// Deserialize a PDU with the format of the given room version.
+    ///
+    /// Unlike the `Deserialize` implementation, which guesses the format from the presence of the
+    /// `event_id` field, this fails if the PDU doesn't match the format used by the room version.
+    /// For unknown room versions, this falls back to the `Deserialize` implementation.
+    pub fn from_raw_for_room_version(
+        json: &RawJsonValue,
+        room_version: &RoomVersionId,
+    ) -> serde_json::Result<Self> {
+        match room_version {
+            RoomVersionId::V1 | RoomVersionId::V2 => from_json_str(json.get()).map(Self::RoomV1Pdu),
+            RoomVersionId::V3
+            | RoomVersionId::V4
+            | RoomVersionId::V5
+            | RoomVersionId::V6
+            | RoomVersionId::V7
+            | RoomVersionId::V8
+            | RoomVersionId::V9
+            | RoomVersionId::V10
+            | RoomVersionId::V11 => from_json_str(json.get()).map(Self::RoomV3Pdu),
+            _ => from_json_str(json.get()),
+        }
+    }
+}
+
 /// A 'persistent data unit' (event) for room versions 1 and 2.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[allow(clippy::exhaustive_structs)]
@@ -158,6 +185,105 @@ impl EventHash {
     }
 }
 
+/// The outcome of the checks performed by a homeserver on receipt of a PDU.
+///
+/// This is not part of the PDU itself. It is meant to be persisted alongside a PDU so that
+/// different parts of a server, like federation ingestion and state resolution, agree on whether
+/// an event can be used.
+///
+/// See the [spec] for the checks that lead to each outcome.
+///
+/// [spec]: https://spec.matrix.org/latest/server-server-api/#checks-performed-on-receipt-of-a-pdu
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PduAcceptance {
+    /// The PDU passed all checks.
+    Accepted,
+
+    /// The PDU passed the checks based on its auth events and on the state before it, but failed
+    /// the check based on the current state of the room.
+    ///
+    /// Soft-failed events are persisted and may be used as auth events, but they must not be
+    /// relayed to clients or used as `prev_events` of new events.
+    SoftFailed,
+
+    /// The PDU failed one of the checks that lead to a rejection.
+    ///
+    /// Rejected events must not be relayed to clients and must not be used as auth events or
+    /// `prev_events` of new events.
+    Rejected {
+        /// The reason why the PDU was rejected.
+        reason: PduRejectionReason,
+    },
+}
+
+impl PduAcceptance {
+    /// Creates a new `PduAcceptance::Rejected` with the given reason.
+    pub fn rejected(reason: PduRejectionReason) -> Self {
+        Self::Rejected { reason }
+    }
+
+    /// Whether the PDU was accepted.
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accepted)
+    }
+
+    /// Whether the PDU was soft-failed.
+    pub fn is_soft_failed(&self) -> bool {
+        matches!(self, Self::SoftFailed)
+    }
+
+    /// Whether the PDU was rejected.
+    pub fn is_rejected(&self) -> bool {
+        matches!(self, Self::Rejected { .. })
+    }
+
+    /// The reason why the PDU was rejected, if it was.
+    pub fn rejection_reason(&self) -> Option<&PduRejectionReason> {
+        match self {
+            Self::Rejected { reason } => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Whether the PDU can be used as an auth event for other events.
+    ///
+    /// This is the case for accepted and soft-failed PDUs.
+    pub fn can_be_auth_event(&self) -> bool {
+        !self.is_rejected()
+    }
+}
+
+/// The reason why a PDU was rejected.
+///
+/// Note that a PDU that fails the hash check is not rejected, but redacted before further
+/// processing.
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
+#[derive(Clone, PartialEq, Eq, StringEnum)]
+#[ruma_enum(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum PduRejectionReason {
+    /// The PDU is not a valid event for its room version, or is missing required fields.
+    InvalidFormat,
+
+    /// The PDU doesn't have a valid signature from the sending server or another required
+    /// server.
+    InvalidSignature,
+
+    /// The PDU is not allowed by the authorization rules applied to its auth events.
+    ///
+    /// This also covers the case where one of its auth events was rejected.
+    NotAllowedByAuthEvents,
+
+    /// The PDU is not allowed by the authorization rules applied to the state of the room before
+    /// the event.
+    NotAllowedByStateBeforeEvent,
+
+    #[doc(hidden)]
+    _Custom(PrivOwnedStr),
+}
+
 impl<'de> Deserialize<'de> for Pdu {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -220,10 +220,14 @@ pub struct BundledMessageLikeRelations<E> {
     has_invalid_replacement: bool,
 
     /// Thread relation.
+    ///
+    /// If the thread relation fails to deserialize, this is `None`.
     #[serde(rename = "m.thread", skip_serializing_if = "Option::is_none")]
     pub thread: Option<Box<BundledThread>>,
 
     /// Reference relations.
+    ///
+    /// If the reference relations fail to deserialize, this is `None`.
     #[serde(rename = "m.reference", skip_serializing_if = "Option::is_none")]
     pub reference: Option<Box<ReferenceChunk>>,
 }
@@ -271,11 +275,25 @@ impl<E> Default for BundledMessageLikeRelations<E> {
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub struct BundledStateRelations {
     /// Thread relation.
-    #[serde(rename = "m.thread", skip_serializing_if = "Option::is_none")]
+    ///
+    /// If the thread relation fails to deserialize, this is `None`.
+    #[serde(
+        rename = "m.thread",
+        default,
+        deserialize_with = "crate::none_on_invalid",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub thread: Option<Box<BundledThread>>,
 
     /// Reference relations.
-    #[serde(rename = "m.reference", skip_serializing_if = "Option::is_none")]
+    ///
+    /// If the reference relations fail to deserialize, this is `None`.
+    #[serde(
+        rename = "m.reference",
+        default,
+        deserialize_with = "crate::none_on_invalid",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub reference: Option<Box<ReferenceChunk>>,
 }
 
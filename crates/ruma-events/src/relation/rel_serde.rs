@@ -7,9 +7,9 @@ use super::{BundledMessageLikeRelations, BundledThread, ReferenceChunk};
 struct BundledMessageLikeRelationsJsonRepr<E> {
     #[serde(rename = "m.replace")]
     replace: Option<Raw<Box<E>>>,
-    #[serde(rename = "m.thread")]
+    #[serde(rename = "m.thread", default, deserialize_with = "crate::none_on_invalid")]
     thread: Option<Box<BundledThread>>,
-    #[serde(rename = "m.reference")]
+    #[serde(rename = "m.reference", default, deserialize_with = "crate::none_on_invalid")]
     reference: Option<Box<ReferenceChunk>>,
 }
 
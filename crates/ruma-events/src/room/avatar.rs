@@ -24,6 +24,15 @@ pub struct RoomAvatarEventContent {
     pub info: Option<Box<ImageInfo>>,
 
     /// URL of the avatar image.
+    ///
+    /// If this is `None`, the room has no avatar. If you activate the `compat-empty-string-null`
+    /// feature, this field being an empty string in JSON will result in `None` here during
+    /// deserialization.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "compat-empty-string-null",
+        serde(default, deserialize_with = "ruma_common::serde::empty_string_as_none")
+    )]
     pub url: Option<OwnedMxcUri>,
 }
 
@@ -32,6 +41,18 @@ impl RoomAvatarEventContent {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Create a `RoomAvatarEventContent` that removes the avatar of the room.
+    ///
+    /// It is serialized as `{}`.
+    pub fn removal() -> Self {
+        Self::default()
+    }
+
+    /// Whether this content doesn't set an avatar, i.e. it removes the avatar of the room.
+    pub fn is_empty(&self) -> bool {
+        self.url.is_none()
+    }
 }
 
 /// Metadata about an image (specific to avatars).
@@ -77,3 +98,35 @@ impl ImageInfo {
         Self::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::owned_mxc_uri;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::RoomAvatarEventContent;
+
+    #[test]
+    fn serialize_removal() {
+        let content = RoomAvatarEventContent::removal();
+        assert!(content.is_empty());
+        assert_eq!(to_json_value(content).unwrap(), json!({}));
+    }
+
+    #[test]
+    fn deserialize_removal() {
+        let content = from_json_value::<RoomAvatarEventContent>(json!({})).unwrap();
+        assert!(content.is_empty());
+
+        let content = from_json_value::<RoomAvatarEventContent>(json!({ "url": null })).unwrap();
+        assert!(content.is_empty());
+    }
+
+    #[test]
+    fn serialize_avatar() {
+        let mut content = RoomAvatarEventContent::new();
+        content.url = Some(owned_mxc_uri!("mxc://example.org/avatar"));
+        assert!(!content.is_empty());
+        assert_eq!(to_json_value(content).unwrap(), json!({ "url": "mxc://example.org/avatar" }));
+    }
+}
@@ -5,7 +5,10 @@
 use std::{borrow::Cow, collections::BTreeMap};
 
 use js_int::UInt;
-use ruma_common::{serde::JsonObject, OwnedDeviceId, OwnedEventId};
+use ruma_common::{
+    serde::{JsonObject, Raw},
+    EventEncryptionAlgorithm, OwnedDeviceId, OwnedEventId,
+};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +16,7 @@ use super::message;
 use crate::relation::{Annotation, CustomRelation, InReplyTo, Reference, RelationType, Thread};
 
 mod relation_serde;
+mod scheme_serde;
 
 /// The content of an `m.room.encrypted` event.
 #[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
@@ -33,6 +37,41 @@ impl RoomEncryptedEventContent {
     pub fn new(scheme: EncryptedEventScheme, relates_to: Option<Relation>) -> Self {
         Self { scheme, relates_to }
     }
+
+    /// Creates a new `RoomEncryptedEventContent` with the given scheme and the relation of the
+    /// given plaintext event content.
+    ///
+    /// The relation of an encrypted event is sent in cleartext, so that the homeserver can
+    /// aggregate it. This copies the `m.relates_to` field of the content that was encrypted with
+    /// the given scheme.
+    pub fn with_plaintext_relation<T>(
+        scheme: EncryptedEventScheme,
+        plaintext: &Raw<T>,
+    ) -> serde_json::Result<Self> {
+        let relates_to = plaintext.get_field("m.relates_to")?;
+        Ok(Self { scheme, relates_to })
+    }
+
+    /// Applies the relation of this event to the given decrypted event content.
+    ///
+    /// According to the spec, the cleartext relation of an encrypted event takes precedence over
+    /// any `m.relates_to` field in its encrypted payload. This replaces the `m.relates_to` field of
+    /// the decrypted content with the relation of this event, or removes it if this event doesn't
+    /// have a relation.
+    pub fn apply_relation_to<T>(&self, decrypted: &Raw<T>) -> serde_json::Result<Raw<T>> {
+        let mut content = decrypted.deserialize_as::<JsonObject>()?;
+
+        match &self.relates_to {
+            Some(relates_to) => {
+                content.insert("m.relates_to".to_owned(), serde_json::to_value(relates_to)?);
+            }
+            None => {
+                content.remove("m.relates_to");
+            }
+        }
+
+        Ok(Raw::new(&content)?.cast())
+    }
 }
 
 impl From<EncryptedEventScheme> for RoomEncryptedEventContent {
@@ -65,19 +104,26 @@ impl From<EncryptedEventScheme> for ToDeviceRoomEncryptedEventContent {
 }
 
 /// The encryption scheme for `RoomEncryptedEventContent`.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
-#[serde(tag = "algorithm")]
 pub enum EncryptedEventScheme {
     /// An event encrypted with `m.olm.v1.curve25519-aes-sha2`.
-    #[serde(rename = "m.olm.v1.curve25519-aes-sha2")]
     OlmV1Curve25519AesSha2(OlmV1Curve25519AesSha2Content),
 
     /// An event encrypted with `m.megolm.v1.aes-sha2`.
-    #[serde(rename = "m.megolm.v1.aes-sha2")]
     MegolmV1AesSha2(MegolmV1AesSha2Content),
 }
 
+impl EncryptedEventScheme {
+    /// The algorithm used to encrypt the event.
+    pub fn algorithm(&self) -> EventEncryptionAlgorithm {
+        match self {
+            Self::OlmV1Curve25519AesSha2(_) => EventEncryptionAlgorithm::OlmV1Curve25519AesSha2,
+            Self::MegolmV1AesSha2(_) => EventEncryptionAlgorithm::MegolmV1AesSha2,
+        }
+    }
+}
+
 /// Relationship information about an encrypted event.
 ///
 /// Outside of the encrypted payload to support server aggregation.
@@ -272,7 +318,7 @@ impl From<MegolmV1AesSha2ContentInit> for MegolmV1AesSha2Content {
 mod tests {
     use assert_matches2::assert_matches;
     use js_int::uint;
-    use ruma_common::{owned_event_id, serde::Raw};
+    use ruma_common::{owned_event_id, serde::Raw, EventEncryptionAlgorithm};
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::{
@@ -331,6 +377,7 @@ mod tests {
 
         let content: RoomEncryptedEventContent = from_json_value(json_data).unwrap();
 
+        assert_eq!(content.scheme.algorithm(), EventEncryptionAlgorithm::MegolmV1AesSha2);
         assert_matches!(content.scheme, EncryptedEventScheme::MegolmV1AesSha2(scheme));
         assert_eq!(scheme.ciphertext, "ciphertext");
         assert_eq!(scheme.sender_key, "sender_key");
@@ -355,6 +402,7 @@ mod tests {
         });
         let content: RoomEncryptedEventContent = from_json_value(json_data).unwrap();
 
+        assert_eq!(content.scheme.algorithm(), EventEncryptionAlgorithm::OlmV1Curve25519AesSha2);
         assert_matches!(content.scheme, EncryptedEventScheme::OlmV1Curve25519AesSha2(c));
         assert_eq!(c.sender_key, "test_key");
         assert_eq!(c.ciphertext.len(), 1);
@@ -0,0 +1,58 @@
+use ruma_common::{serde::JsonObject, EventEncryptionAlgorithm};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value as JsonValue;
+
+use super::EncryptedEventScheme;
+
+impl<'de> Deserialize<'de> for EncryptedEventScheme {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut fields = JsonObject::deserialize(deserializer)?;
+        let algorithm =
+            fields.remove("algorithm").ok_or_else(|| de::Error::missing_field("algorithm"))?;
+        let algorithm =
+            EventEncryptionAlgorithm::deserialize(algorithm).map_err(de::Error::custom)?;
+        let fields = JsonValue::Object(fields);
+
+        match algorithm {
+            EventEncryptionAlgorithm::OlmV1Curve25519AesSha2 => {
+                serde_json::from_value(fields).map(Self::OlmV1Curve25519AesSha2)
+            }
+            EventEncryptionAlgorithm::MegolmV1AesSha2 => {
+                serde_json::from_value(fields).map(Self::MegolmV1AesSha2)
+            }
+            _ => {
+                return Err(de::Error::custom(format!(
+                    "unknown encryption algorithm `{algorithm}`"
+                )))
+            }
+        }
+        .map_err(de::Error::custom)
+    }
+}
+
+#[derive(Serialize)]
+struct EncryptedEventSchemeSerHelper<'a, T> {
+    algorithm: EventEncryptionAlgorithm,
+    #[serde(flatten)]
+    content: &'a T,
+}
+
+impl Serialize for EncryptedEventScheme {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let algorithm = self.algorithm();
+        match self {
+            Self::OlmV1Curve25519AesSha2(content) => {
+                EncryptedEventSchemeSerHelper { algorithm, content }.serialize(serializer)
+            }
+            Self::MegolmV1AesSha2(content) => {
+                EncryptedEventSchemeSerHelper { algorithm, content }.serialize(serializer)
+            }
+        }
+    }
+}
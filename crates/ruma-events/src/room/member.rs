@@ -7,8 +7,8 @@ use std::collections::BTreeMap;
 use js_int::Int;
 use ruma_common::{
     serde::{CanBeEmpty, Raw, StringEnum},
-    OwnedMxcUri, OwnedServerName, OwnedServerSigningKeyId, OwnedTransactionId, OwnedUserId,
-    RoomVersionId, UserId,
+    OwnedEventId, OwnedMxcUri, OwnedServerName, OwnedServerSigningKeyId, OwnedTransactionId,
+    OwnedUserId, RoomVersionId, UserId,
 };
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
@@ -19,9 +19,13 @@ use crate::{
 };
 
 mod change;
+mod display_name;
 
 use self::change::membership_change;
-pub use self::change::{Change, MembershipChange, MembershipDetails};
+pub use self::{
+    change::{Change, MembershipChange, MembershipDetails},
+    display_name::{DisambiguationReason, DisplayName},
+};
 
 /// The content of an `m.room.member` event.
 ///
@@ -540,8 +544,14 @@ pub struct RoomMemberUnsigned {
     pub transaction_id: Option<OwnedTransactionId>,
 
     /// Optional previous content of the event.
+    ///
+    /// If the previous content fails to deserialize, this is `None`.
+    #[serde(default, deserialize_with = "crate::none_on_invalid")]
     pub prev_content: Option<PossiblyRedactedRoomMemberEventContent>,
 
+    /// The ID of the state event replaced by this event, if any.
+    pub replaces_state: Option<OwnedEventId>,
+
     /// State events to assist the receiver in identifying the room.
     #[serde(default)]
     pub invite_room_state: Vec<Raw<AnyStrippedStateEvent>>,
@@ -570,6 +580,7 @@ impl CanBeEmpty for RoomMemberUnsigned {
         self.age.is_none()
             && self.transaction_id.is_none()
             && self.prev_content.is_none()
+            && self.replaces_state.is_none()
             && self.invite_room_state.is_empty()
             && self.relations.is_empty()
     }
@@ -658,6 +669,32 @@ mod tests {
         assert_matches!(prev_content.third_party_invite, None);
     }
 
+    #[test]
+    fn serde_with_invalid_prev_content() {
+        let json = json!({
+            "type": "m.room.member",
+            "content": {
+                "membership": "join"
+            },
+            "event_id": "$h29iv0s8:example.com",
+            "origin_server_ts": 1,
+            "room_id": "!n8f893n9:example.com",
+            "sender": "@carl:example.com",
+            "state_key": "@carl:example.com",
+            "unsigned": {
+                "prev_content": {
+                    "displayname": "Carl",
+                },
+                "replaces_state": "$prevstate:example.com",
+            },
+        });
+
+        let ev = from_json_value::<OriginalStateEvent<RoomMemberEventContent>>(json).unwrap();
+        assert_eq!(ev.content.membership, MembershipState::Join);
+        assert!(ev.prev_content().is_none());
+        assert_eq!(ev.unsigned.replaces_state.unwrap(), "$prevstate:example.com");
+    }
+
     #[test]
     fn serde_with_content_full() {
         let json = json!({
@@ -0,0 +1,158 @@
+use std::fmt;
+
+use ruma_common::UserId;
+
+/// The name to show for a room member.
+///
+/// This implements the algorithm to [calculate the display name for a user], including
+/// disambiguation of display names that could be mistaken for a user ID.
+///
+/// Use its `Display` implementation to get the string to render.
+///
+/// [calculate the display name for a user]: https://spec.matrix.org/latest/client-server-api/#calculating-the-display-name-for-a-user
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum DisplayName<'a> {
+    /// The member doesn't have a display name, so their user ID should be used.
+    UserId(&'a UserId),
+
+    /// The member's display name is unique in the room.
+    Unique(&'a str),
+
+    /// The member's display name must be shown along with their user ID.
+    Disambiguated {
+        /// The display name of the member.
+        display_name: &'a str,
+
+        /// The user ID of the member.
+        user_id: &'a UserId,
+
+        /// Why the display name needs to be disambiguated.
+        reason: DisambiguationReason,
+    },
+}
+
+impl<'a> DisplayName<'a> {
+    /// Calculate the name to show for the member with the given user ID and display name.
+    ///
+    /// `others_with_same_name` are the users with the same display name among the members of the
+    /// room whose membership is `join` or `invite`. It may include `user_id`, which is ignored.
+    pub fn new<'b>(
+        user_id: &'a UserId,
+        display_name: Option<&'a str>,
+        others_with_same_name: impl IntoIterator<Item = &'b UserId>,
+    ) -> Self {
+        let Some(display_name) = display_name.filter(|name| !name.is_empty()) else {
+            return Self::UserId(user_id);
+        };
+
+        let reason = if looks_like_user_id(display_name) {
+            DisambiguationReason::LooksLikeUserId
+        } else if others_with_same_name.into_iter().any(|other| other != user_id) {
+            DisambiguationReason::Ambiguous
+        } else {
+            return Self::Unique(display_name);
+        };
+
+        Self::Disambiguated { display_name, user_id, reason }
+    }
+
+    /// Whether the member's display name could be used to impersonate another user.
+    pub fn is_spoofing_attempt(&self) -> bool {
+        matches!(self, Self::Disambiguated { reason: DisambiguationReason::LooksLikeUserId, .. })
+    }
+}
+
+impl fmt::Display for DisplayName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UserId(user_id) => write!(f, "{user_id}"),
+            Self::Unique(display_name) => f.write_str(display_name),
+            Self::Disambiguated { display_name, user_id, .. } => {
+                write!(f, "{display_name} ({user_id})")
+            }
+        }
+    }
+}
+
+/// The reason why a [`DisplayName`] was disambiguated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum DisambiguationReason {
+    /// Another member of the room uses the same display name.
+    Ambiguous,
+
+    /// The display name looks like a user ID.
+    LooksLikeUserId,
+}
+
+/// Whether the given display name could be mistaken for a user ID.
+///
+/// Leading whitespace is ignored, since it is usually not visible.
+fn looks_like_user_id(display_name: &str) -> bool {
+    let name = display_name.trim_start();
+    name.starts_with('@') && name.contains(':')
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches2::assert_matches;
+    use ruma_common::user_id;
+
+    use super::{DisambiguationReason, DisplayName};
+
+    #[test]
+    fn no_display_name() {
+        let alice = user_id!("@alice:example.org");
+
+        let name = DisplayName::new(alice, None, []);
+        assert_eq!(name, DisplayName::UserId(alice));
+        assert_eq!(name.to_string(), "@alice:example.org");
+
+        let name = DisplayName::new(alice, Some(""), []);
+        assert_eq!(name, DisplayName::UserId(alice));
+    }
+
+    #[test]
+    fn unique_display_name() {
+        let alice = user_id!("@alice:example.org");
+
+        let name = DisplayName::new(alice, Some("Alice"), [alice]);
+        assert_eq!(name, DisplayName::Unique("Alice"));
+        assert_eq!(name.to_string(), "Alice");
+        assert!(!name.is_spoofing_attempt());
+    }
+
+    #[test]
+    fn shared_display_name() {
+        let alice = user_id!("@alice:example.org");
+        let other = user_id!("@alice:example.com");
+
+        let name = DisplayName::new(alice, Some("Alice"), [alice, other]);
+        assert_matches!(
+            name,
+            DisplayName::Disambiguated { reason: DisambiguationReason::Ambiguous, .. }
+        );
+        assert_eq!(name.to_string(), "Alice (@alice:example.org)");
+        assert!(!name.is_spoofing_attempt());
+    }
+
+    #[test]
+    fn display_name_looks_like_user_id() {
+        let mallory = user_id!("@mallory:example.org");
+
+        let name = DisplayName::new(mallory, Some("@alice:example.org"), []);
+        assert_matches!(
+            name,
+            DisplayName::Disambiguated { reason: DisambiguationReason::LooksLikeUserId, .. }
+        );
+        assert_eq!(name.to_string(), "@alice:example.org (@mallory:example.org)");
+        assert!(name.is_spoofing_attempt());
+
+        let name = DisplayName::new(mallory, Some(" @alice:example.org"), []);
+        assert!(name.is_spoofing_attempt());
+
+        let name = DisplayName::new(mallory, Some("@mallory"), []);
+        assert_eq!(name, DisplayName::Unique("@mallory"));
+    }
+}
@@ -52,13 +52,20 @@ fn get_message_quote_fallbacks(original_event: OriginalEventData<'_>) -> (String
     #[cfg(not(feature = "html"))]
     let html_body = FormattedOrPlainBody { formatted, body };
 
+    // Identifiers using historical grammars can contain characters that need to be escaped in HTML.
+    let event_uri = room_id.matrix_to_event_uri(event_id.to_owned()).to_string();
+    let event_uri = EscapeHtmlEntities(&event_uri);
+    let sender_uri = sender.matrix_to_uri().to_string();
+    let sender_uri = EscapeHtmlEntities(&sender_uri);
+    let sender_html = EscapeHtmlEntities(sender.as_str());
+
     (
         format!("> {emote_sign}<{sender}> {body}").replace('\n', "\n> "),
         format!(
             "<mx-reply>\
                 <blockquote>\
-                    <a href=\"https://matrix.to/#/{room_id}/{event_id}\">In reply to</a> \
-                    {emote_sign}<a href=\"https://matrix.to/#/{sender}\">{sender}</a>\
+                    <a href=\"{event_uri}\">In reply to</a> \
+                    {emote_sign}<a href=\"{sender_uri}\">{sender_html}</a>\
                     <br>\
                     {html_body}\
                 </blockquote>\
@@ -176,4 +183,32 @@ mod tests {
             </mx-reply>",
         );
     }
+
+    #[test]
+    fn fallback_escape_historical_sender() {
+        let (plain_quote, html_quote) = super::get_message_quote_fallbacks(
+            (&OriginalRoomMessageEvent {
+                content: RoomMessageEventContent::text_plain("Hello"),
+                event_id: owned_event_id!("$1598361704261elfgc:localhost"),
+                sender: owned_user_id!("@<b>&\"alice\"</b>:example.com"),
+                origin_server_ts: MilliSecondsSinceUnixEpoch::now(),
+                room_id: owned_room_id!("!n8f893n9:example.com"),
+                unsigned: MessageLikeUnsigned::new(),
+            })
+                .into(),
+        );
+
+        assert_eq!(plain_quote, "> <@<b>&\"alice\"</b>:example.com> Hello");
+        assert_eq!(
+            html_quote,
+            "<mx-reply>\
+                <blockquote>\
+                    <a href=\"https://matrix.to/#/!n8f893n9:example.com/$1598361704261elfgc:localhost\">In reply to</a> \
+                    <a href=\"https://matrix.to/#/@%3Cb%3E&amp;%22alice%22%3C%2Fb%3E:example.com\">@&lt;b&gt;&amp;&quot;alice&quot;&lt;/b&gt;:example.com</a>\
+                    <br>\
+                    Hello\
+                </blockquote>\
+            </mx-reply>",
+        );
+    }
 }
@@ -15,6 +15,14 @@ use crate::EmptyStateKey;
 #[ruma_event(type = "m.room.name", kind = State, state_key_type = EmptyStateKey)]
 pub struct RoomNameEventContent {
     /// The name of the room.
+    ///
+    /// If this is empty, the room has no name. It is omitted during serialization in that case,
+    /// and it is empty if it is absent or `null` in JSON during deserialization.
+    #[serde(
+        default,
+        deserialize_with = "ruma_common::serde::none_as_default",
+        skip_serializing_if = "String::is_empty"
+    )]
     pub name: String,
 }
 
@@ -23,6 +31,18 @@ impl RoomNameEventContent {
     pub fn new(name: String) -> Self {
         Self { name }
     }
+
+    /// Create a `RoomNameEventContent` that removes the name of the room.
+    ///
+    /// It is serialized as `{}`.
+    pub fn removal() -> Self {
+        Self { name: String::new() }
+    }
+
+    /// Whether this content doesn't set a name, i.e. it removes the name of the room.
+    pub fn is_empty(&self) -> bool {
+        self.name.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -66,4 +86,17 @@ mod tests {
             "The room name"
         );
     }
+
+    #[test]
+    fn removal() {
+        let content = RoomNameEventContent::removal();
+        assert!(content.is_empty());
+        assert_eq!(to_json_value(content).unwrap(), json!({}));
+
+        let content = from_json_value::<RoomNameEventContent>(json!({})).unwrap();
+        assert!(content.is_empty());
+
+        let content = from_json_value::<RoomNameEventContent>(json!({ "name": null })).unwrap();
+        assert!(content.is_empty());
+    }
 }
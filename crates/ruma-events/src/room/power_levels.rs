@@ -6,7 +6,7 @@ use std::{cmp::max, collections::BTreeMap};
 
 use js_int::{int, Int};
 use ruma_common::{
-    power_levels::{default_power_level, NotificationPowerLevels},
+    power_levels::{default_creator_power_level, default_power_level, NotificationPowerLevels},
     push::PushConditionPowerLevelsCtx,
     OwnedUserId, RoomVersionId, UserId,
 };
@@ -126,6 +126,16 @@ impl RoomPowerLevelsEventContent {
             notifications: NotificationPowerLevels::default(),
         }
     }
+
+    /// Creates a new `RoomPowerLevelsEventContent` for a new room created by the given user.
+    ///
+    /// All the fields have their default values, except `users` which gives the creator the
+    /// power level they have implicitly before the room has an `m.room.power_levels` event.
+    pub fn bootstrap(creator: OwnedUserId) -> Self {
+        let mut content = Self::new();
+        content.users.insert(creator, default_creator_power_level());
+        content
+    }
 }
 
 impl Default for RoomPowerLevelsEventContent {
@@ -728,6 +738,21 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn bootstrap() {
+        let power_levels =
+            RoomPowerLevelsEventContent::bootstrap(user_id!("@creator:example.com").to_owned());
+
+        assert_eq!(
+            to_json_value(&power_levels).unwrap(),
+            json!({
+                "users": {
+                    "@creator:example.com": 100,
+                },
+            })
+        );
+    }
+
     #[test]
     fn serialization_with_all_fields() {
         let user = user_id!("@carl:example.com");
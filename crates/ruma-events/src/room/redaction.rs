@@ -17,6 +17,7 @@ use tracing::error;
 use crate::{
     BundledMessageLikeRelations, EventContent, MessageLikeEventType, RedactContent,
     RedactedMessageLikeEventContent, RedactedUnsigned, StaticEventContent,
+    UnsignedRoomRedactionEvent,
 };
 
 mod event_serde;
@@ -316,6 +317,24 @@ impl RoomRedactionEvent {
     pub fn as_original(&self) -> Option<&OriginalRoomRedactionEvent> {
         as_variant!(self, Self::Original)
     }
+
+    /// Whether this event is redacted.
+    pub fn is_redacted(&self) -> bool {
+        matches!(self, Self::Redacted(_))
+    }
+
+    /// Returns this event's `redacted_because` from inside `unsigned`, if it is redacted.
+    pub fn redacted_because(&self) -> Option<&UnsignedRoomRedactionEvent> {
+        match self {
+            Self::Original(_) => None,
+            Self::Redacted(ev) => Some(&ev.unsigned.redacted_because),
+        }
+    }
+
+    /// Returns the `reason` of this event's redaction, if it is redacted and has one.
+    pub fn redaction_reason(&self) -> Option<&str> {
+        self.redacted_because()?.content.reason.as_deref()
+    }
 }
 
 impl SyncRoomRedactionEvent {
@@ -369,6 +388,24 @@ impl SyncRoomRedactionEvent {
         as_variant!(self, Self::Original)
     }
 
+    /// Whether this event is redacted.
+    pub fn is_redacted(&self) -> bool {
+        matches!(self, Self::Redacted(_))
+    }
+
+    /// Returns this event's `redacted_because` from inside `unsigned`, if it is redacted.
+    pub fn redacted_because(&self) -> Option<&UnsignedRoomRedactionEvent> {
+        match self {
+            Self::Original(_) => None,
+            Self::Redacted(ev) => Some(&ev.unsigned.redacted_because),
+        }
+    }
+
+    /// Returns the `reason` of this event's redaction, if it is redacted and has one.
+    pub fn redaction_reason(&self) -> Option<&str> {
+        self.redacted_because()?.content.reason.as_deref()
+    }
+
     /// Convert this sync event into a full event (one with a `room_id` field).
     pub fn into_full_event(self, room_id: OwnedRoomId) -> RoomRedactionEvent {
         match self {
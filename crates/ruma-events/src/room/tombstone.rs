@@ -2,10 +2,13 @@
 //!
 //! [`m.room.tombstone`]: https://spec.matrix.org/latest/client-server-api/#mroomtombstone
 
-use ruma_common::OwnedRoomId;
+use std::collections::BTreeSet;
+
+use ruma_common::{OwnedRoomId, RoomId};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
+use super::create::RoomCreateEventContent;
 use crate::{
     EmptyStateKey, EventContent, PossiblyRedactedStateEventContent, StateEventType,
     StaticEventContent,
@@ -70,3 +73,210 @@ impl PossiblyRedactedStateEventContent for PossiblyRedactedRoomTombstoneEventCon
 impl StaticEventContent for PossiblyRedactedRoomTombstoneEventContent {
     const TYPE: &'static str = "m.room.tombstone";
 }
+
+/// The links of a room to the rooms it was upgraded from and to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct RoomUpgradeLinks {
+    /// The room this room replaces, from the `predecessor` of its `m.room.create` event.
+    pub predecessor: Option<OwnedRoomId>,
+
+    /// The room that replaces this room, from the `replacement_room` of its `m.room.tombstone`
+    /// event.
+    pub successor: Option<OwnedRoomId>,
+}
+
+impl RoomUpgradeLinks {
+    /// Creates a new `RoomUpgradeLinks` with the given predecessor and successor.
+    pub fn new(predecessor: Option<OwnedRoomId>, successor: Option<OwnedRoomId>) -> Self {
+        Self { predecessor, successor }
+    }
+
+    /// Creates a new `RoomUpgradeLinks` from the `m.room.create` and `m.room.tombstone` events of
+    /// a room.
+    pub fn from_state(
+        create: &RoomCreateEventContent,
+        tombstone: Option<&RoomTombstoneEventContent>,
+    ) -> Self {
+        Self {
+            predecessor: create.predecessor.as_ref().map(|predecessor| predecessor.room_id.clone()),
+            successor: tombstone.map(|tombstone| tombstone.replacement_room.clone()),
+        }
+    }
+}
+
+/// Walk the chain of upgrades of a room in both directions.
+///
+/// `fetch_links` is called with the ID of each room of the chain, and should return its
+/// [`RoomUpgradeLinks`], or `None` if the state of the room is not available.
+///
+/// Anyone can create a room with an arbitrary `predecessor`, or send a tombstone with an arbitrary
+/// `replacement_room` in a room they control, so a link is only followed if the linked room links
+/// back: a predecessor must have the current room as its successor, and a successor must have the
+/// current room as its predecessor. The walk stops in a direction when a room has no link in that
+/// direction, when the state of the linked room is not available, when the linked room doesn't
+/// link back, or when a room that was already visited is reached again, to protect against
+/// cycles.
+///
+/// Returns the IDs of the rooms of the chain, from the oldest to the most recent. The list always
+/// contains `room_id`.
+pub fn room_upgrade_chain(
+    room_id: &RoomId,
+    mut fetch_links: impl FnMut(&RoomId) -> Option<RoomUpgradeLinks>,
+) -> Vec<OwnedRoomId> {
+    let mut visited = BTreeSet::from([room_id.to_owned()]);
+    let links = fetch_links(room_id).unwrap_or_default();
+
+    let mut predecessors =
+        walk_upgrade_links(room_id, links.predecessor, &mut visited, &mut fetch_links, |links| {
+            (links.predecessor, links.successor)
+        });
+    let successors =
+        walk_upgrade_links(room_id, links.successor, &mut visited, &mut fetch_links, |links| {
+            (links.successor, links.predecessor)
+        });
+
+    predecessors.reverse();
+    predecessors.push(room_id.to_owned());
+    predecessors.extend(successors);
+    predecessors
+}
+
+/// Walk the chain of upgrades of a room in one direction, starting at `next`.
+///
+/// `split_links` returns the link to follow and the link that must point back to the previous
+/// room.
+fn walk_upgrade_links(
+    room_id: &RoomId,
+    mut next: Option<OwnedRoomId>,
+    visited: &mut BTreeSet<OwnedRoomId>,
+    fetch_links: &mut impl FnMut(&RoomId) -> Option<RoomUpgradeLinks>,
+    split_links: impl Fn(RoomUpgradeLinks) -> (Option<OwnedRoomId>, Option<OwnedRoomId>),
+) -> Vec<OwnedRoomId> {
+    let mut rooms = Vec::new();
+    let mut previous = room_id.to_owned();
+
+    while let Some(room) = next.take() {
+        if visited.contains(&room) {
+            break;
+        }
+
+        let Some((forward, back)) = fetch_links(&room).map(&split_links) else {
+            break;
+        };
+        if back.as_ref() != Some(&previous) {
+            break;
+        }
+
+        visited.insert(room.clone());
+        rooms.push(room.clone());
+        previous = room;
+        next = forward;
+    }
+
+    rooms
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ruma_common::{owned_room_id, room_id, OwnedRoomId};
+
+    use super::{room_upgrade_chain, RoomUpgradeLinks};
+
+    fn links(
+        pairs: &[(&str, Option<&str>, Option<&str>)],
+    ) -> BTreeMap<OwnedRoomId, RoomUpgradeLinks> {
+        pairs
+            .iter()
+            .map(|(room, predecessor, successor)| {
+                (
+                    OwnedRoomId::try_from(*room).unwrap(),
+                    RoomUpgradeLinks::new(
+                        predecessor.map(|id| id.try_into().unwrap()),
+                        successor.map(|id| id.try_into().unwrap()),
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn walk_chain() {
+        let links = links(&[
+            ("!a:localhost", None, Some("!b:localhost")),
+            ("!b:localhost", Some("!a:localhost"), Some("!c:localhost")),
+            ("!c:localhost", Some("!b:localhost"), None),
+        ]);
+
+        for room in ["!a:localhost", "!b:localhost", "!c:localhost"] {
+            let chain = room_upgrade_chain(room.try_into().unwrap(), |id| links.get(id).cloned());
+            assert_eq!(
+                chain,
+                [
+                    owned_room_id!("!a:localhost"),
+                    owned_room_id!("!b:localhost"),
+                    owned_room_id!("!c:localhost")
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn walk_chain_with_unknown_room() {
+        let links = links(&[("!b:localhost", Some("!a:localhost"), Some("!c:localhost"))]);
+
+        let chain = room_upgrade_chain(room_id!("!b:localhost"), |id| links.get(id).cloned());
+        assert_eq!(chain, [owned_room_id!("!b:localhost")]);
+
+        let chain = room_upgrade_chain(room_id!("!z:localhost"), |id| links.get(id).cloned());
+        assert_eq!(chain, [owned_room_id!("!z:localhost")]);
+    }
+
+    #[test]
+    fn walk_chain_with_one_sided_links() {
+        // `!evil` claims to be the predecessor of `!b` and the successor of `!a`, but they don't
+        // link back to it.
+        let rooms = links(&[
+            ("!a:localhost", None, Some("!b:localhost")),
+            ("!b:localhost", Some("!a:localhost"), None),
+            ("!evil:localhost", Some("!a:localhost"), Some("!b:localhost")),
+        ]);
+
+        let chain = room_upgrade_chain(room_id!("!evil:localhost"), |id| rooms.get(id).cloned());
+        assert_eq!(chain, [owned_room_id!("!evil:localhost")]);
+
+        // `!b` claims to replace `!evil`, and `!a` claims to be replaced by `!evil`, but `!evil`
+        // links to other rooms.
+        let other_rooms = links(&[
+            ("!a:localhost", None, Some("!evil:localhost")),
+            ("!b:localhost", Some("!evil:localhost"), None),
+            ("!evil:localhost", Some("!c:localhost"), Some("!c:localhost")),
+        ]);
+
+        let chain = room_upgrade_chain(room_id!("!a:localhost"), |id| other_rooms.get(id).cloned());
+        assert_eq!(chain, [owned_room_id!("!a:localhost")]);
+        let chain = room_upgrade_chain(room_id!("!b:localhost"), |id| other_rooms.get(id).cloned());
+        assert_eq!(chain, [owned_room_id!("!b:localhost")]);
+    }
+
+    #[test]
+    fn walk_chain_with_cycle() {
+        let links = links(&[
+            ("!a:localhost", Some("!c:localhost"), Some("!b:localhost")),
+            ("!b:localhost", Some("!a:localhost"), Some("!c:localhost")),
+            ("!c:localhost", Some("!b:localhost"), Some("!a:localhost")),
+        ]);
+
+        let chain = room_upgrade_chain(room_id!("!a:localhost"), |id| links.get(id).cloned());
+        assert_eq!(
+            chain,
+            [
+                owned_room_id!("!b:localhost"),
+                owned_room_id!("!c:localhost"),
+                owned_room_id!("!a:localhost"),
+            ]
+        );
+    }
+}
@@ -2,4 +2,5 @@
 
 pub mod default_key;
 pub mod key;
+pub mod recovery_key;
 pub mod secret;
@@ -7,7 +7,7 @@ use std::borrow::Cow;
 use js_int::{uint, UInt};
 use ruma_common::{
     serde::{Base64, JsonObject},
-    KeyDerivationAlgorithm,
+    KeyDerivationAlgorithm, SecretEncryptionAlgorithm,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -104,11 +104,11 @@ pub enum SecretStorageEncryptionAlgorithm {
 }
 
 impl SecretStorageEncryptionAlgorithm {
-    /// The `algorithm` string.
-    pub fn algorithm(&self) -> &str {
+    /// The algorithm used to encrypt the secret.
+    pub fn algorithm(&self) -> SecretEncryptionAlgorithm {
         match self {
-            Self::V1AesHmacSha2(_) => "m.secret_storage.v1.aes-hmac-sha2",
-            Self::_Custom(c) => &c.algorithm,
+            Self::V1AesHmacSha2(_) => SecretEncryptionAlgorithm::SecretStorageV1AesHmacSha2,
+            Self::_Custom(c) => c.algorithm.as_str().into(),
         }
     }
 
@@ -416,7 +416,7 @@ mod tests {
         assert_matches!(content.passphrase, None);
 
         let algorithm = content.algorithm;
-        assert_eq!(algorithm.algorithm(), "io.ruma.custom_alg");
+        assert_eq!(algorithm.algorithm().as_str(), "io.ruma.custom_alg");
         let properties = algorithm.properties();
         assert_eq!(properties.len(), 2);
         assert_eq!(
@@ -1,45 +1,33 @@
+use ruma_common::SecretEncryptionAlgorithm;
 use serde::{de, Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
-use super::{
-    CustomSecretEncryptionAlgorithm, SecretStorageEncryptionAlgorithm,
-    SecretStorageV1AesHmacSha2Properties,
-};
-
-#[derive(Deserialize)]
-#[serde(untagged)]
-enum SecretStorageEncryptionAlgorithmDeHelper {
-    Known(KnownSecretStorageEncryptionAlgorithmDeHelper),
-    Unknown(CustomSecretEncryptionAlgorithm),
-}
-
-#[derive(Deserialize)]
-#[serde(tag = "algorithm")]
-enum KnownSecretStorageEncryptionAlgorithmDeHelper {
-    #[serde(rename = "m.secret_storage.v1.aes-hmac-sha2")]
-    V1AesHmacSha2(SecretStorageV1AesHmacSha2Properties),
-}
+use super::{CustomSecretEncryptionAlgorithm, SecretStorageEncryptionAlgorithm};
 
 impl<'de> Deserialize<'de> for SecretStorageEncryptionAlgorithm {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: de::Deserializer<'de>,
     {
-        let helper = SecretStorageEncryptionAlgorithmDeHelper::deserialize(deserializer)?;
+        let custom = CustomSecretEncryptionAlgorithm::deserialize(deserializer)?;
 
-        Ok(match helper {
-            SecretStorageEncryptionAlgorithmDeHelper::Known(k) => match k {
-                KnownSecretStorageEncryptionAlgorithmDeHelper::V1AesHmacSha2(p) => {
-                    Self::V1AesHmacSha2(p)
+        // Known algorithms with invalid properties are kept as custom algorithms, to avoid losing
+        // data.
+        Ok(match SecretEncryptionAlgorithm::from(custom.algorithm.as_str()) {
+            SecretEncryptionAlgorithm::SecretStorageV1AesHmacSha2 => {
+                match serde_json::from_value(JsonValue::Object(custom.properties.clone())) {
+                    Ok(properties) => Self::V1AesHmacSha2(properties),
+                    Err(_) => Self::_Custom(custom),
                 }
-            },
-            SecretStorageEncryptionAlgorithmDeHelper::Unknown(c) => Self::_Custom(c),
+            }
+            _ => Self::_Custom(custom),
         })
     }
 }
 
 #[derive(Debug, Serialize)]
-struct SecretStorageEncryptionAlgorithmSerHelper<'a, T: Serialize> {
-    algorithm: &'a str,
+struct SecretStorageEncryptionAlgorithmSerHelper<T: Serialize> {
+    algorithm: SecretEncryptionAlgorithm,
     #[serde(flatten)]
     properties: T,
 }
@@ -0,0 +1,208 @@
+//! Encoding and decoding of secret storage keys as [recovery keys].
+//!
+//! [recovery keys]: https://spec.matrix.org/latest/client-server-api/#key-representation
+
+use std::fmt;
+
+/// The bytes prepended to the key before encoding it.
+const PREFIX: [u8; 2] = [0x8B, 0x01];
+
+/// The length of a secret storage key, in bytes.
+const KEY_LENGTH: usize = 32;
+
+/// The Bitcoin base58 alphabet.
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// A secret storage key, that can be represented as a recovery key.
+///
+/// The recovery key is the base58 encoding of the key, with a prefix and a parity byte, formatted
+/// into groups of four characters separated by spaces.
+///
+/// The `Debug` implementation of this type doesn't print the key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RecoveryKey([u8; KEY_LENGTH]);
+
+impl RecoveryKey {
+    /// Creates a `RecoveryKey` from the bytes of the key.
+    pub fn from_bytes(bytes: [u8; KEY_LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    /// The bytes of the key.
+    pub fn as_bytes(&self) -> &[u8; KEY_LENGTH] {
+        &self.0
+    }
+
+    /// Decodes a recovery key.
+    ///
+    /// Whitespace is ignored.
+    pub fn from_base58(s: &str) -> Result<Self, RecoveryKeyError> {
+        let bytes = base58_decode(s.bytes().filter(|b| !b.is_ascii_whitespace()))?;
+
+        if bytes.len() != PREFIX.len() + KEY_LENGTH + 1 {
+            return Err(RecoveryKeyError::WrongLength);
+        }
+        if bytes[..PREFIX.len()] != PREFIX {
+            return Err(RecoveryKeyError::WrongPrefix);
+        }
+        if bytes.iter().fold(0, |parity, byte| parity ^ byte) != 0 {
+            return Err(RecoveryKeyError::ParityMismatch);
+        }
+
+        let mut key = [0; KEY_LENGTH];
+        key.copy_from_slice(&bytes[PREFIX.len()..PREFIX.len() + KEY_LENGTH]);
+        Ok(Self(key))
+    }
+
+    /// Encodes this key as a recovery key.
+    pub fn to_base58(&self) -> String {
+        let mut bytes = Vec::with_capacity(PREFIX.len() + KEY_LENGTH + 1);
+        bytes.extend_from_slice(&PREFIX);
+        bytes.extend_from_slice(&self.0);
+        bytes.push(bytes.iter().fold(0, |parity, byte| parity ^ byte));
+
+        let encoded = base58_encode(&bytes);
+        let mut s = String::with_capacity(encoded.len() + encoded.len() / 4);
+        for (i, c) in encoded.chars().enumerate() {
+            if i > 0 && i % 4 == 0 {
+                s.push(' ');
+            }
+            s.push(c);
+        }
+
+        s
+    }
+}
+
+impl fmt::Debug for RecoveryKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecoveryKey").finish_non_exhaustive()
+    }
+}
+
+/// An error encountered when decoding a [`RecoveryKey`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum RecoveryKeyError {
+    /// The string contains a character that is not in the base58 alphabet.
+    #[error("invalid base58 character")]
+    NonBase58Character,
+
+    /// The decoded data doesn't have the length of a recovery key.
+    #[error("invalid length")]
+    WrongLength,
+
+    /// The decoded data doesn't start with the recovery key prefix.
+    #[error("invalid prefix")]
+    WrongPrefix,
+
+    /// The parity byte doesn't match the decoded data.
+    #[error("invalid parity")]
+    ParityMismatch,
+}
+
+fn base58_encode(bytes: &[u8]) -> String {
+    // Digits in base 58, least significant first.
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+
+    for &byte in bytes {
+        let mut carry = u32::from(byte);
+        for digit in &mut digits {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    // Leading zeros are encoded as the first character of the alphabet.
+    let leading_zeros = bytes.iter().take_while(|&&b| b == 0).count();
+
+    std::iter::repeat(BASE58_ALPHABET[0])
+        .take(leading_zeros)
+        .chain(digits.iter().rev().map(|&d| BASE58_ALPHABET[usize::from(d)]))
+        .map(char::from)
+        .collect()
+}
+
+fn base58_decode(chars: impl Iterator<Item = u8>) -> Result<Vec<u8>, RecoveryKeyError> {
+    // Bytes, least significant first.
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut leading_zeros = 0;
+    let mut is_leading = true;
+
+    for c in chars {
+        let value = BASE58_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or(RecoveryKeyError::NonBase58Character)?;
+
+        if is_leading && value == 0 {
+            leading_zeros += 1;
+            continue;
+        }
+        is_leading = false;
+
+        let mut carry = value as u32;
+        for byte in &mut bytes {
+            carry += u32::from(*byte) * 58;
+            *byte = (carry & 0xFF) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xFF) as u8);
+            carry >>= 8;
+        }
+    }
+
+    bytes.extend(std::iter::repeat(0).take(leading_zeros));
+    bytes.reverse();
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RecoveryKey, RecoveryKeyError};
+
+    #[test]
+    fn roundtrip() {
+        let key = RecoveryKey::from_bytes(std::array::from_fn(|i| i as u8));
+        let encoded = key.to_base58();
+
+        assert_eq!(RecoveryKey::from_base58(&encoded).unwrap(), key);
+        assert_eq!(RecoveryKey::from_base58(&encoded.replace(' ', "")).unwrap(), key);
+    }
+
+    #[test]
+    fn encode() {
+        assert_eq!(
+            RecoveryKey::from_bytes([0; 32]).to_base58(),
+            "EsSz ygLv VP1b xF1C v7kE eBQx MxDP buG5 w25T L3b6 hfyG Kkrd"
+        );
+        assert_eq!(
+            RecoveryKey::from_bytes(std::array::from_fn(|i| i as u8)).to_base58(),
+            "EsSz ykH7 LCZx 7Cae cmKD wcmY JRXi Ybtu 8iQ3 t8Ez nRwK pUY1"
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let encoded = RecoveryKey::from_bytes([0; 32]).to_base58();
+
+        assert_eq!(
+            RecoveryKey::from_base58(&encoded.replace('E', "0")).unwrap_err(),
+            RecoveryKeyError::NonBase58Character
+        );
+        assert_eq!(
+            RecoveryKey::from_base58(&encoded[..encoded.len() - 4]).unwrap_err(),
+            RecoveryKeyError::WrongLength
+        );
+        assert_eq!(
+            RecoveryKey::from_base58(&encoded.replace("Kkrd", "Kkre")).unwrap_err(),
+            RecoveryKeyError::ParityMismatch
+        );
+    }
+}
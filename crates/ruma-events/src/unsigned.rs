@@ -59,6 +59,7 @@ impl<C: MessageLikeEventContent> CanBeEmpty for MessageLikeUnsigned<C> {
 
 /// Extra information about a state event that is not incorporated into the event's hash.
 #[derive(Clone, Debug, Deserialize)]
+#[serde(bound = "C: DeserializeOwned")]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub struct StateUnsigned<C: PossiblyRedactedStateEventContent> {
     /// The time in milliseconds that has elapsed since the event was sent.
@@ -73,8 +74,14 @@ pub struct StateUnsigned<C: PossiblyRedactedStateEventContent> {
     pub transaction_id: Option<OwnedTransactionId>,
 
     /// Optional previous content of the event.
+    ///
+    /// If the previous content fails to deserialize, this is `None`.
+    #[serde(default, deserialize_with = "crate::none_on_invalid")]
     pub prev_content: Option<C>,
 
+    /// The ID of the state event replaced by this event, if any.
+    pub replaces_state: Option<OwnedEventId>,
+
     /// [Bundled aggregations] of related child events.
     ///
     /// [Bundled aggregations]: https://spec.matrix.org/latest/client-server-api/#aggregations-of-child-events
@@ -85,7 +92,13 @@ pub struct StateUnsigned<C: PossiblyRedactedStateEventContent> {
 impl<C: PossiblyRedactedStateEventContent> StateUnsigned<C> {
     /// Create a new `Unsigned` with fields set to `None`.
     pub fn new() -> Self {
-        Self { age: None, transaction_id: None, prev_content: None, relations: Default::default() }
+        Self {
+            age: None,
+            transaction_id: None,
+            prev_content: None,
+            replaces_state: None,
+            relations: Default::default(),
+        }
     }
 }
 
@@ -99,6 +112,7 @@ impl<C: PossiblyRedactedStateEventContent> CanBeEmpty for StateUnsigned<C> {
         self.age.is_none()
             && self.transaction_id.is_none()
             && self.prev_content.is_none()
+            && self.replaces_state.is_none()
             && self.relations.is_empty()
     }
 }
@@ -0,0 +1,118 @@
+#![cfg(feature = "unstable-msc2346")]
+
+use assert_matches2::assert_matches;
+use ruma_common::{mxc_uri, owned_mxc_uri, owned_user_id, user_id};
+use ruma_events::{
+    bridge::{BridgeEventContent, BridgeInfo},
+    AnyStateEvent, StateEvent,
+};
+use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+#[test]
+fn serialize_content() {
+    let mut protocol = BridgeInfo::new("irc".to_owned());
+    protocol.displayname = Some("IRC".to_owned());
+    let mut network = BridgeInfo::new("libera".to_owned());
+    network.displayname = Some("Libera Chat".to_owned());
+    network.avatar_url = Some(owned_mxc_uri!("mxc://example.org/libera"));
+    network.external_url = Some("https://libera.chat".to_owned());
+    let mut content = BridgeEventContent::new(
+        owned_user_id!("@appservice-irc:matrix.org"),
+        protocol,
+        BridgeInfo::new("#ruma".to_owned()),
+    );
+    content.creator = Some(owned_user_id!("@alice:matrix.org"));
+    content.network = Some(network);
+
+    assert_eq!(
+        to_json_value(&content).unwrap(),
+        json!({
+            "bridgebot": "@appservice-irc:matrix.org",
+            "creator": "@alice:matrix.org",
+            "protocol": {
+                "id": "irc",
+                "displayname": "IRC",
+            },
+            "network": {
+                "id": "libera",
+                "displayname": "Libera Chat",
+                "avatar_url": "mxc://example.org/libera",
+                "external_url": "https://libera.chat",
+            },
+            "channel": {
+                "id": "#ruma",
+            },
+        })
+    );
+}
+
+#[test]
+fn deserialize_event() {
+    let json = json!({
+        "content": {
+            "bridgebot": "@appservice-irc:matrix.org",
+            "protocol": {
+                "id": "irc",
+                "displayname": "IRC",
+            },
+            "network": {
+                "id": "libera",
+                "avatar_url": "mxc://example.org/libera",
+            },
+            "channel": {
+                "id": "#ruma",
+                "external_url": "https://web.libera.chat/#ruma",
+            },
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!n8f893n9:example.com",
+        "sender": "@appservice-irc:matrix.org",
+        "state_key": "org.matrix.appservice-irc://irc/libera/#ruma",
+        "type": "uk.half-shot.bridge",
+    });
+
+    assert_matches!(
+        from_json_value::<AnyStateEvent>(json),
+        Ok(AnyStateEvent::Bridge(StateEvent::Original(ev)))
+    );
+    assert_eq!(ev.state_key, "org.matrix.appservice-irc://irc/libera/#ruma");
+
+    let content = ev.content;
+    assert_eq!(content.bridgebot, user_id!("@appservice-irc:matrix.org"));
+    assert_eq!(content.creator, None);
+    assert_eq!(content.protocol.id, "irc");
+    assert_eq!(content.protocol.displayname.as_deref(), Some("IRC"));
+    let network = content.network.unwrap();
+    assert_eq!(network.id, "libera");
+    assert_eq!(network.avatar_url.as_deref(), Some(mxc_uri!("mxc://example.org/libera")));
+    assert_eq!(content.channel.id, "#ruma");
+    assert_eq!(content.channel.external_url.as_deref(), Some("https://web.libera.chat/#ruma"));
+}
+
+#[test]
+fn deserialize_stable_event_type() {
+    let json = json!({
+        "content": {
+            "bridgebot": "@appservice-irc:matrix.org",
+            "protocol": {
+                "id": "irc",
+            },
+            "channel": {
+                "id": "#ruma",
+            },
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!n8f893n9:example.com",
+        "sender": "@appservice-irc:matrix.org",
+        "state_key": "org.matrix.appservice-irc://irc/libera/#ruma",
+        "type": "m.bridge",
+    });
+
+    assert_matches!(
+        from_json_value::<AnyStateEvent>(json),
+        Ok(AnyStateEvent::Bridge(StateEvent::Original(ev)))
+    );
+    assert_eq!(ev.content.channel.id, "#ruma");
+}
@@ -2,9 +2,12 @@ use assert_matches2::assert_matches;
 use ruma_common::{owned_device_id, owned_event_id, serde::Raw};
 use ruma_events::{
     relation::{Annotation, CustomRelation, InReplyTo, Reference, Thread},
-    room::encrypted::{
-        EncryptedEventScheme, MegolmV1AesSha2ContentInit, Relation, Replacement,
-        RoomEncryptedEventContent,
+    room::{
+        encrypted::{
+            EncryptedEventScheme, MegolmV1AesSha2ContentInit, Relation, Replacement,
+            RoomEncryptedEventContent,
+        },
+        message::{Relation as MessageRelation, RoomMessageEventContent},
     },
 };
 use serde_json::{
@@ -620,3 +623,80 @@ fn custom_serialization_roundtrip() {
     assert_eq!(deser_relation.get("event_id").unwrap().as_str().unwrap(), event_id);
     assert_eq!(deser_relation.get("key").unwrap().as_str().unwrap(), key);
 }
+
+#[test]
+fn content_with_plaintext_relation() {
+    let plaintext = Raw::new(&json!({
+        "body": "* Hello, World!",
+        "msgtype": "m.text",
+        "m.new_content": {
+            "body": "Hello, World!",
+            "msgtype": "m.text",
+        },
+        "m.relates_to": {
+            "rel_type": "m.replace",
+            "event_id": "$replaced",
+        },
+    }))
+    .unwrap()
+    .cast::<RoomMessageEventContent>();
+
+    let content =
+        RoomEncryptedEventContent::with_plaintext_relation(encrypted_scheme(), &plaintext).unwrap();
+    assert_matches!(content.relates_to, Some(Relation::Replacement(Replacement { event_id, .. })));
+    assert_eq!(event_id, "$replaced");
+
+    let plaintext = Raw::new(&json!({
+        "body": "Hello, World!",
+        "msgtype": "m.text",
+    }))
+    .unwrap()
+    .cast::<RoomMessageEventContent>();
+
+    let content =
+        RoomEncryptedEventContent::with_plaintext_relation(encrypted_scheme(), &plaintext).unwrap();
+    assert_matches!(content.relates_to, None);
+}
+
+#[test]
+fn content_apply_relation_to_decrypted() {
+    let decrypted = Raw::new(&json!({
+        "body": "Hello, World!",
+        "msgtype": "m.text",
+        "m.relates_to": {
+            "rel_type": "m.thread",
+            "event_id": "$wrong_thread_root",
+        },
+    }))
+    .unwrap()
+    .cast::<RoomMessageEventContent>();
+
+    let content = RoomEncryptedEventContent::new(
+        encrypted_scheme(),
+        Some(Relation::Thread(Thread::without_fallback(owned_event_id!("$thread_root")))),
+    );
+    let decrypted_with_relation = content.apply_relation_to(&decrypted).unwrap();
+    assert_eq!(
+        decrypted_with_relation.deserialize_as::<JsonValue>().unwrap(),
+        json!({
+            "body": "Hello, World!",
+            "msgtype": "m.text",
+            "m.relates_to": {
+                "rel_type": "m.thread",
+                "event_id": "$thread_root",
+            },
+        })
+    );
+    let message = decrypted_with_relation.deserialize().unwrap();
+    assert_matches!(message.relates_to, Some(MessageRelation::Thread(thread)));
+    assert_eq!(thread.event_id, "$thread_root");
+
+    let content = RoomEncryptedEventContent::new(encrypted_scheme(), None);
+    assert_eq!(
+        content.apply_relation_to(&decrypted).unwrap().deserialize_as::<JsonValue>().unwrap(),
+        json!({
+            "body": "Hello, World!",
+            "msgtype": "m.text",
+        })
+    );
+}
@@ -1,6 +1,9 @@
 use assert_matches2::assert_matches;
 use js_int::int;
-use ruma_common::{room_alias_id, serde::test::serde_json_eq};
+use ruma_common::{
+    owned_event_id, owned_room_id, owned_user_id, room_alias_id, room_id,
+    serde::test::serde_json_eq,
+};
 use ruma_events::{
     room::{
         aliases::RoomAliasesEventContent,
@@ -8,7 +11,7 @@ use ruma_events::{
         power_levels::RoomPowerLevelsEventContent,
     },
     AnyEphemeralRoomEvent, AnyMessageLikeEvent, AnyStateEvent, AnySyncMessageLikeEvent,
-    AnySyncStateEvent, AnySyncTimelineEvent, AnyTimelineEvent, EphemeralRoomEventType,
+    AnySyncStateEvent, AnySyncTimelineEvent, AnyTimelineEvent, DedupKey, EphemeralRoomEventType,
     GlobalAccountDataEventType, MessageLikeEvent, MessageLikeEventType, OriginalMessageLikeEvent,
     OriginalStateEvent, OriginalSyncMessageLikeEvent, OriginalSyncStateEvent,
     RoomAccountDataEventType, StateEvent, StateEventType, SyncMessageLikeEvent, SyncStateEvent,
@@ -282,3 +285,67 @@ fn serialize_and_deserialize_from_display_form() {
     serde_json_eq(StateEventType::RoomCreate, json!("m.room.create"));
     serde_json_eq(EphemeralRoomEventType::Typing, json!("m.typing"));
 }
+
+#[test]
+fn dedup_key() {
+    let mut json_data = message_event_sync();
+    json_data["unsigned"]["transaction_id"] = "txn1".into();
+    let sync_event = from_json_value::<AnySyncTimelineEvent>(json_data).unwrap();
+    let key = sync_event.dedup_key(room_id!("!room:room.com"));
+
+    let local_echo = DedupKey::for_transaction(
+        owned_room_id!("!room:room.com"),
+        owned_user_id!("@example:localhost"),
+        "txn0".into(),
+    );
+    assert!(!key.is_duplicate_of(&local_echo));
+
+    let local_echo = DedupKey::for_transaction(
+        owned_room_id!("!room:room.com"),
+        owned_user_id!("@example:localhost"),
+        "txn1".into(),
+    );
+    assert!(key.is_duplicate_of(&local_echo));
+    assert!(local_echo.is_duplicate_of(&key));
+
+    let event = from_json_value::<AnyTimelineEvent>(message_event()).unwrap();
+    let key = event.dedup_key();
+    assert_eq!(key.transaction_id, None);
+    assert!(!key.is_duplicate_of(&local_echo));
+
+    let sent = DedupKey::for_event(
+        owned_room_id!("!room:room.com"),
+        owned_user_id!("@example:localhost"),
+        owned_event_id!("$152037280074GZeOm:localhost"),
+    );
+    assert!(key.is_duplicate_of(&sent));
+
+    let other_room = DedupKey::for_event(
+        owned_room_id!("!other:room.com"),
+        owned_user_id!("@example:localhost"),
+        owned_event_id!("$152037280074GZeOm:localhost"),
+    );
+    assert!(!key.is_duplicate_of(&other_room));
+}
+
+#[test]
+fn stable_event_type_name() {
+    assert_eq!(StateEventType::RoomCreate.stable_name().as_deref(), Some("m.room.create"));
+    assert_eq!(
+        MessageLikeEventType::CallSdpStreamMetadataChanged.stable_name().as_deref(),
+        Some("m.call.sdp_stream_metadata_changed")
+    );
+    assert_eq!(
+        GlobalAccountDataEventType::from("m.secret_storage.key.my_key").stable_name().as_deref(),
+        Some("m.secret_storage.key.my_key")
+    );
+    assert_eq!(StateEventType::from("m.custom").stable_name().as_deref(), Some("m.custom"));
+    assert_eq!(StateEventType::from("org.example.custom").stable_name(), None);
+
+    #[cfg(feature = "unstable-msc2346")]
+    {
+        let event_type = StateEventType::from("m.bridge");
+        assert_eq!(event_type.to_string(), "uk.half-shot.bridge");
+        assert_eq!(event_type.stable_name().as_deref(), Some("m.bridge"));
+    }
+}
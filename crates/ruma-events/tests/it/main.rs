@@ -1,6 +1,7 @@
 mod audio;
 mod beacon;
 mod beacon_info;
+mod bridge;
 mod call;
 mod encrypted;
 mod enums;
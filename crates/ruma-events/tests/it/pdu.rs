@@ -2,13 +2,14 @@
 
 use std::collections::BTreeMap;
 
+use assert_matches2::assert_matches;
 use js_int::uint;
 use ruma_common::{
     event_id, owned_event_id, owned_room_id, owned_server_signing_key_id, owned_user_id,
-    server_name, MilliSecondsSinceUnixEpoch,
+    server_name, MilliSecondsSinceUnixEpoch, RoomVersionId,
 };
 use ruma_events::{
-    pdu::{EventHash, Pdu, RoomV1Pdu, RoomV3Pdu},
+    pdu::{EventHash, Pdu, PduAcceptance, PduRejectionReason, RoomV1Pdu, RoomV3Pdu},
     TimelineEventType,
 };
 use serde_json::{
@@ -237,3 +238,76 @@ fn deserialize_pdu_as_v3() {
         _ => unreachable!("new PDU version"),
     }
 }
+
+#[test]
+fn deserialize_pdu_for_room_version() {
+    let json = to_raw_json_value(&json!({
+        "auth_events": [
+            "$abc123:matrix.org"
+        ],
+        "content": {
+            "key": "value"
+        },
+        "depth": 12,
+        "hashes": {
+            "sha256": "ThisHashCoversAllFieldsInCaseThisIsRedacted"
+        },
+        "origin_server_ts": 1_234_567_890,
+        "prev_events": [
+                "$abc123:matrix.org"
+        ],
+        "room_id": "!abc123:matrix.org",
+        "sender": "@someone:matrix.org",
+        "signatures": {
+            "example.com": {
+                "ed25519:key_version": "86BytesOfSignatureOfTheRedactedEvent"
+            }
+        },
+        "type": "m.room.message",
+    }))
+    .unwrap();
+
+    assert_matches!(
+        Pdu::from_raw_for_room_version(&json, &RoomVersionId::V10),
+        Ok(Pdu::RoomV3Pdu(_))
+    );
+    Pdu::from_raw_for_room_version(&json, &RoomVersionId::V1).unwrap_err();
+    assert_matches!(
+        Pdu::from_raw_for_room_version(&json, &"org.example.custom".try_into().unwrap()),
+        Ok(Pdu::RoomV3Pdu(_))
+    );
+}
+
+#[test]
+fn serialize_pdu_acceptance() {
+    assert_eq!(to_json_value(PduAcceptance::Accepted).unwrap(), json!({ "status": "accepted" }));
+    assert_eq!(
+        to_json_value(PduAcceptance::SoftFailed).unwrap(),
+        json!({ "status": "soft_failed" })
+    );
+    assert_eq!(
+        to_json_value(PduAcceptance::rejected(PduRejectionReason::NotAllowedByAuthEvents)).unwrap(),
+        json!({ "status": "rejected", "reason": "not_allowed_by_auth_events" })
+    );
+}
+
+#[test]
+fn deserialize_pdu_acceptance() {
+    let acceptance = from_json_value::<PduAcceptance>(json!({ "status": "soft_failed" })).unwrap();
+    assert!(acceptance.is_soft_failed());
+    assert!(acceptance.can_be_auth_event());
+
+    let acceptance = from_json_value::<PduAcceptance>(
+        json!({ "status": "rejected", "reason": "invalid_signature" }),
+    )
+    .unwrap();
+    assert!(acceptance.is_rejected());
+    assert!(!acceptance.can_be_auth_event());
+    assert_eq!(acceptance.rejection_reason(), Some(&PduRejectionReason::InvalidSignature));
+
+    let acceptance = from_json_value::<PduAcceptance>(
+        json!({ "status": "rejected", "reason": "org.example.custom" }),
+    )
+    .unwrap();
+    assert_eq!(acceptance.rejection_reason().unwrap().as_str(), "org.example.custom");
+}
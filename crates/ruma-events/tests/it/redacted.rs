@@ -9,7 +9,7 @@ use ruma_events::{
     },
     AnyMessageLikeEvent, AnySyncMessageLikeEvent, AnySyncStateEvent, AnySyncTimelineEvent,
     AnyTimelineEvent, EventContentFromType, MessageLikeEvent, RedactContent, SyncMessageLikeEvent,
-    SyncStateEvent,
+    SyncStateEvent, UnsignedRoomRedactionEvent,
 };
 use serde_json::{
     from_value as from_json_value, json, to_value as to_json_value,
@@ -110,6 +110,36 @@ fn deserialize_redacted_any_room_sync() {
     assert_eq!(redacted.event_id, "$h29iv0s8:example.com");
 }
 
+#[test]
+fn redaction_accessors() {
+    let redacted = json!({
+        "content": {},
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "sender": "@carl:example.com",
+        "unsigned": unsigned(),
+        "type": "m.room.message",
+    });
+    let redacted = from_json_value::<AnySyncTimelineEvent>(redacted).unwrap();
+
+    assert!(redacted.is_redacted());
+    assert_eq!(redacted.redacted_because().unwrap().sender, "@carl:example.com");
+    assert_eq!(redacted.redaction_reason(), Some("redacted because"));
+
+    let original = json!({
+        "content": { "msgtype": "m.text", "body": "Hello" },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "sender": "@carl:example.com",
+        "type": "m.room.message",
+    });
+    let original = from_json_value::<AnySyncTimelineEvent>(original).unwrap();
+
+    assert!(!original.is_redacted());
+    assert!(original.redacted_because().is_none());
+    assert_eq!(original.redaction_reason(), None);
+}
+
 #[test]
 #[allow(deprecated)]
 fn deserialize_redacted_state_event() {
@@ -229,3 +259,59 @@ fn redact_state_content() {
     );
     assert_eq!(creator.unwrap(), "@carl:example.com");
 }
+
+#[test]
+fn redact_original_sync_message_like_event() {
+    let json = json!({
+        "content": {
+            "body": "test",
+            "msgtype": "m.text",
+        },
+        "event_id": "$143273582443PhrSn:example.org",
+        "origin_server_ts": 1,
+        "sender": "@example:example.org",
+        "type": "m.room.message",
+    });
+    let redacted_because: UnsignedRoomRedactionEvent =
+        from_json_value(unsigned()["redacted_because"].clone()).unwrap();
+
+    assert_matches!(
+        from_json_value::<SyncMessageLikeEvent<RoomMessageEventContent>>(json),
+        Ok(SyncMessageLikeEvent::Original(original))
+    );
+    let redacted = original.redact(redacted_because, &RoomVersionId::V11);
+
+    assert_eq!(redacted.event_id, "$143273582443PhrSn:example.org");
+    assert_eq!(redacted.unsigned.redacted_because.event_id, "$h29iv0s8:example.com");
+    assert_eq!(to_json_value(redacted.content).unwrap(), json!({}));
+}
+
+#[test]
+fn redact_original_sync_state_event() {
+    let json = json!({
+        "content": {
+            "room_version": "11",
+            "m.federate": false,
+        },
+        "event_id": "$143273582443PhrSn:example.org",
+        "origin_server_ts": 1,
+        "sender": "@example:example.org",
+        "state_key": "",
+        "type": "m.room.create",
+    });
+    let redacted_because: UnsignedRoomRedactionEvent =
+        from_json_value(unsigned()["redacted_because"].clone()).unwrap();
+
+    assert_matches!(
+        from_json_value::<SyncStateEvent<RoomCreateEventContent>>(json),
+        Ok(SyncStateEvent::Original(original))
+    );
+
+    let redacted = original.clone().redact(redacted_because.clone(), &RoomVersionId::V10);
+    assert!(redacted.content.federate);
+    assert_eq!(redacted.content.room_version, RoomVersionId::V1);
+
+    let redacted = original.redact(redacted_because, &RoomVersionId::V11);
+    assert!(!redacted.content.federate);
+    assert_eq!(redacted.content.room_version, RoomVersionId::V11);
+}
@@ -1,9 +1,11 @@
 use assert_matches2::assert_matches;
 use assign::assign;
+use js_int::uint;
 use ruma_common::{owned_event_id, serde::Raw};
 use ruma_events::{
     relation::{CustomRelation, InReplyTo, Replacement, Thread},
-    room::message::{MessageType, Relation, RoomMessageEventContent},
+    room::message::{MessageType, OriginalSyncRoomMessageEvent, Relation, RoomMessageEventContent},
+    BundledMessageLikeRelations, BundledStateRelations,
 };
 use serde_json::{
     from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
@@ -387,3 +389,65 @@ fn custom_serialization_roundtrip() {
     assert_eq!(deser_relation.get("event_id").unwrap().as_str().unwrap(), event_id);
     assert_eq!(deser_relation.get("key").unwrap().as_str().unwrap(), key);
 }
+
+#[test]
+fn bundled_relations_deserialize() {
+    let json = json!({
+        "m.thread": {
+            "latest_event": {
+                "content": {
+                    "body": "Hello",
+                    "msgtype": "m.text",
+                },
+                "event_id": "$latest_event",
+                "origin_server_ts": 1,
+                "room_id": "!room:localhost",
+                "sender": "@alice:localhost",
+                "type": "m.room.message",
+            },
+            "count": 7,
+            "current_user_participated": true,
+        },
+        "m.reference": {
+            "chunk": [{ "event_id": "$referencing_event" }],
+        },
+    });
+
+    let relations =
+        from_json_value::<BundledMessageLikeRelations<OriginalSyncRoomMessageEvent>>(json).unwrap();
+    let thread = relations.thread.unwrap();
+    assert_eq!(thread.count, uint!(7));
+    assert!(thread.current_user_participated);
+    let reference = relations.reference.unwrap();
+    assert_eq!(reference.chunk.len(), 1);
+    assert_eq!(reference.chunk[0].event_id, "$referencing_event");
+}
+
+#[test]
+fn bundled_relations_deserialize_invalid() {
+    let json = json!({
+        "m.thread": {
+            "count": 7,
+        },
+        "m.reference": {
+            "chunk": [{ "event_id": "$referencing_event" }],
+        },
+    });
+
+    let relations =
+        from_json_value::<BundledMessageLikeRelations<OriginalSyncRoomMessageEvent>>(json).unwrap();
+    assert!(relations.thread.is_none());
+    assert_eq!(relations.reference.unwrap().chunk[0].event_id, "$referencing_event");
+
+    let json = json!({
+        "m.thread": {
+            "count": 7,
+        },
+        "m.reference": {
+            "chunk": "$referencing_event",
+        },
+    });
+
+    let relations = from_json_value::<BundledStateRelations>(json).unwrap();
+    assert!(relations.is_empty());
+}
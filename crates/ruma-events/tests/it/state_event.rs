@@ -54,6 +54,25 @@ fn deserialize_aliases_with_prev_content() {
     assert_eq!(prev_content.aliases.unwrap(), vec![room_alias_id!("#inner:localhost")]);
 }
 
+#[test]
+fn deserialize_aliases_with_invalid_prev_content() {
+    let mut json_data = aliases_event_with_prev_content();
+    json_data["unsigned"] = json!({
+        "prev_content": {
+            "aliases": "#inner:localhost",
+        },
+        "replaces_state": "$prevstate:example.com",
+    });
+
+    assert_matches!(
+        from_json_value::<AnyStateEvent>(json_data),
+        Ok(AnyStateEvent::RoomAliases(StateEvent::Original(ev)))
+    );
+    assert_eq!(ev.content.aliases, vec![room_alias_id!("#somewhere:localhost")]);
+    assert!(ev.unsigned.prev_content.is_none());
+    assert_eq!(ev.unsigned.replaces_state.unwrap(), "$prevstate:example.com");
+}
+
 #[test]
 fn deserialize_aliases_sync_with_room_id() {
     // The same JSON can be used to create a sync event, it just ignores the `room_id` field
@@ -173,3 +192,28 @@ fn deserialize_full_event_convert_to_sync() {
     );
     assert_eq!(sync_ev.sender, "@carl:example.com");
 }
+
+#[test]
+fn deserialize_missing_origin_server_ts() {
+    let json_data = json!({
+        "content": {
+            "aliases": ["#somewhere:localhost"],
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "state_key": "room.com",
+        "type": "m.room.aliases",
+    });
+
+    #[cfg(feature = "compat-optional-origin-server-ts")]
+    {
+        let ev = from_json_value::<AnyStateEvent>(json_data).unwrap();
+        assert_eq!(ev.origin_server_ts(), MilliSecondsSinceUnixEpoch(uint!(0)));
+    }
+
+    #[cfg(not(feature = "compat-optional-origin-server-ts"))]
+    {
+        from_json_value::<AnyStateEvent>(json_data).unwrap_err();
+    }
+}
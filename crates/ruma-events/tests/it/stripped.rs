@@ -62,7 +62,7 @@ fn deserialize_stripped_state_events() {
 
     let ev = from_json_value::<AnyStrippedStateEvent>(name_event).unwrap();
     assert_matches!(ev, AnyStrippedStateEvent::RoomName(ev));
-    assert_eq!(ev.content.name.as_deref(), Some("Ruma"));
+    assert_eq!(ev.content.name, "Ruma");
     assert_eq!(ev.sender.to_string(), "@example:localhost");
 
     let ev = from_json_value::<AnyStrippedStateEvent>(join_rules_event).unwrap();
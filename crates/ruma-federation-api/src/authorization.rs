@@ -1,3 +1,5 @@
 //! Endpoints to retrieve the complete auth chain for a given event.
 
 pub mod get_event_authorization;
+#[cfg(feature = "unstable-unspecified")]
+pub mod query_auth;
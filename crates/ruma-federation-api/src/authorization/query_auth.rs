@@ -0,0 +1,119 @@
+//! `POST /_matrix/federation/*/query_auth/{roomId}/{eventId}`
+//!
+//! Compare the auth chain of an event with the receiving server.
+//!
+//! This endpoint was removed from the specification by [MSC2600], but it can still be needed to
+//! federate with servers that handle rooms using old room versions.
+//!
+//! [MSC2600]: https://github.com/matrix-org/matrix-spec-proposals/pull/2600
+
+pub mod v1 {
+    //! `/v1/` (unspecified)
+
+    use std::collections::BTreeMap;
+
+    use ruma_common::{
+        api::{request, response, Metadata},
+        metadata, OwnedEventId, OwnedRoomId,
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_json::value::RawValue as RawJsonValue;
+
+    const METADATA: Metadata = metadata! {
+        method: POST,
+        rate_limited: false,
+        authentication: ServerSignatures,
+        history: {
+            unstable => "/_matrix/federation/v1/query_auth/:room_id/:event_id",
+        }
+    };
+
+    /// Request type for the `query_auth` endpoint.
+    #[request]
+    pub struct Request {
+        /// The room ID of the event.
+        #[ruma_api(path)]
+        pub room_id: OwnedRoomId,
+
+        /// The event ID to compare the auth chain of.
+        #[ruma_api(path)]
+        pub event_id: OwnedEventId,
+
+        /// The auth chain of the event according to the sending server.
+        pub auth_chain: Vec<Box<RawJsonValue>>,
+
+        /// The IDs of the events that the sending server is missing.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub missing: Vec<OwnedEventId>,
+
+        /// The events that the sending server has rejected, with the reason for the rejection.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        pub rejects: BTreeMap<OwnedEventId, RejectedEvent>,
+    }
+
+    /// Response type for the `query_auth` endpoint.
+    #[response]
+    pub struct Response {
+        /// The auth chain of the event according to the receiving server.
+        pub auth_chain: Vec<Box<RawJsonValue>>,
+
+        /// The IDs of the events that the receiving server is missing.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub missing: Vec<OwnedEventId>,
+
+        /// The events that the receiving server has rejected, with the reason for the rejection.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        pub rejects: BTreeMap<OwnedEventId, RejectedEvent>,
+    }
+
+    impl Request {
+        /// Creates a new `Request` with the given room ID, event ID and auth chain.
+        pub fn new(
+            room_id: OwnedRoomId,
+            event_id: OwnedEventId,
+            auth_chain: Vec<Box<RawJsonValue>>,
+        ) -> Self {
+            Self { room_id, event_id, auth_chain, missing: Vec::new(), rejects: BTreeMap::new() }
+        }
+    }
+
+    impl Response {
+        /// Creates a new `Response` with the given auth chain.
+        pub fn new(auth_chain: Vec<Box<RawJsonValue>>) -> Self {
+            Self { auth_chain, missing: Vec::new(), rejects: BTreeMap::new() }
+        }
+    }
+
+    /// Information about a rejected event.
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+    pub struct RejectedEvent {
+        /// The reason why the event was rejected.
+        pub reason: String,
+    }
+
+    impl RejectedEvent {
+        /// Creates a new `RejectedEvent` with the given reason.
+        pub fn new(reason: String) -> Self {
+            Self { reason }
+        }
+    }
+
+    #[cfg(all(test, feature = "server"))]
+    mod tests {
+        use ruma_common::api::OutgoingResponse;
+        use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+        use super::Response;
+
+        #[test]
+        fn response_body() {
+            let res = Response::new(vec![]).try_into_http_response::<Vec<u8>>().unwrap();
+
+            assert_eq!(
+                from_json_slice::<JsonValue>(res.body()).unwrap(),
+                json!({ "auth_chain": [] })
+            );
+        }
+    }
+}
@@ -17,6 +17,8 @@ pub mod v1 {
         OwnedDeviceId, OwnedUserId,
     };
 
+    use crate::transactions::edu::SigningKeyUpdateContent;
+
     const METADATA: Metadata = metadata! {
         method: POST,
         rate_limited: false,
@@ -65,5 +67,84 @@ pub mod v1 {
         ) -> Self {
             Self { device_keys, ..Default::default() }
         }
+
+        /// Updates the cross-signing keys in this response with the given `m.signing_key_update`
+        /// EDU.
+        ///
+        /// This allows to keep the keys of remote users returned by this endpoint up to date with
+        /// the incremental updates received in transactions. Only the keys present in the update
+        /// are replaced, except that a new master key without a new self-signing key removes the
+        /// previous self-signing key, since it was signed by the previous master key.
+        pub fn apply_signing_key_update(&mut self, update: SigningKeyUpdateContent) {
+            let SigningKeyUpdateContent { user_id, master_key, self_signing_key } = update;
+
+            match (master_key, self_signing_key) {
+                (Some(master_key), Some(self_signing_key)) => {
+                    self.master_keys.insert(user_id.clone(), master_key);
+                    self.self_signing_keys.insert(user_id, self_signing_key);
+                }
+                (Some(master_key), None) => {
+                    self.self_signing_keys.remove(&user_id);
+                    self.master_keys.insert(user_id, master_key);
+                }
+                (None, Some(self_signing_key)) => {
+                    self.self_signing_keys.insert(user_id, self_signing_key);
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ruma_common::{encryption::CrossSigningKey, owned_user_id, serde::Raw};
+        use serde_json::{from_value as from_json_value, json};
+
+        use super::Response;
+        use crate::transactions::edu::SigningKeyUpdateContent;
+
+        fn cross_signing_key(usage: &str, key: &str) -> Raw<CrossSigningKey> {
+            from_json_value(json!({
+                "user_id": "@alice:example.org",
+                "usage": [usage],
+                "keys": { format!("ed25519:{key}"): key },
+            }))
+            .unwrap()
+        }
+
+        #[test]
+        fn apply_signing_key_update() {
+            let user_id = owned_user_id!("@alice:example.org");
+            let mut response = Response::default();
+            response.master_keys.insert(user_id.clone(), cross_signing_key("master", "master1"));
+            response
+                .self_signing_keys
+                .insert(user_id.clone(), cross_signing_key("self_signing", "self1"));
+
+            // Update of the self-signing key only.
+            let mut update = SigningKeyUpdateContent::new(user_id.clone());
+            update.self_signing_key = Some(cross_signing_key("self_signing", "self2"));
+            response.apply_signing_key_update(update);
+
+            assert!(response.master_keys[&user_id].json().get().contains("master1"));
+            assert!(response.self_signing_keys[&user_id].json().get().contains("self2"));
+
+            // Update of the master key only.
+            let mut update = SigningKeyUpdateContent::new(user_id.clone());
+            update.master_key = Some(cross_signing_key("master", "master2"));
+            response.apply_signing_key_update(update);
+
+            assert!(response.master_keys[&user_id].json().get().contains("master2"));
+            assert!(!response.self_signing_keys.contains_key(&user_id));
+
+            // Update of both keys.
+            let mut update = SigningKeyUpdateContent::new(user_id.clone());
+            update.master_key = Some(cross_signing_key("master", "master3"));
+            update.self_signing_key = Some(cross_signing_key("self_signing", "self3"));
+            response.apply_signing_key_update(update);
+
+            assert!(response.master_keys[&user_id].json().get().contains("master3"));
+            assert!(response.self_signing_keys[&user_id].json().get().contains("self3"));
+        }
     }
 }
@@ -51,4 +51,30 @@ pub mod v2 {
             Self { valid }
         }
     }
+
+    #[cfg(all(test, feature = "client"))]
+    mod tests {
+        use ruma_common::{
+            api::{MatrixVersion, OutgoingRequest, SendAccessToken},
+            serde::Base64,
+        };
+
+        use super::Request;
+
+        #[test]
+        fn serialize_request_uri() {
+            let request = Request::new(Base64::new(vec![0xfb, 0xff, 0x01]))
+                .try_into_http_request::<Vec<u8>>(
+                    "https://identity.localhost",
+                    SendAccessToken::None,
+                    &[MatrixVersion::V1_1],
+                )
+                .unwrap();
+
+            assert_eq!(
+                request.uri().to_string(),
+                "https://identity.localhost/_matrix/identity/v2/pubkey/isvalid?public_key=%2B%2F8B"
+            );
+        }
+    }
 }
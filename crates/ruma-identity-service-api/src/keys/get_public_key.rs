@@ -51,4 +51,30 @@ pub mod v2 {
             Self { public_key }
         }
     }
+
+    #[cfg(all(test, feature = "client"))]
+    mod tests {
+        use ruma_common::{
+            api::{MatrixVersion, OutgoingRequest, SendAccessToken},
+            server_signing_key_id,
+        };
+
+        use super::Request;
+
+        #[test]
+        fn serialize_request_uri() {
+            let request = Request::new(server_signing_key_id!("ed25519:0").to_owned())
+                .try_into_http_request::<Vec<u8>>(
+                    "https://identity.localhost",
+                    SendAccessToken::None,
+                    &[MatrixVersion::V1_1],
+                )
+                .unwrap();
+
+            assert_eq!(
+                request.uri().to_string(),
+                "https://identity.localhost/_matrix/identity/v2/pubkey/ed25519:0"
+            );
+        }
+    }
 }
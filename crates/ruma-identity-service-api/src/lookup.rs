@@ -1,6 +1,10 @@
 //! Endpoints to look up Matrix IDs bound to 3PIDs.
 
-use ruma_common::serde::StringEnum;
+use ruma_common::{
+    serde::{base64::UrlSafe, Base64, StringEnum},
+    thirdparty::Medium,
+};
+use sha2::{Digest, Sha256};
 
 use crate::PrivOwnedStr;
 
@@ -27,8 +31,31 @@ pub enum IdentifierHashingAlgorithm {
     _Custom(PrivOwnedStr),
 }
 
+impl IdentifierHashingAlgorithm {
+    /// Encodes the given 3PID address for lookup with this algorithm, as defined in the [spec].
+    ///
+    /// The `pepper` is the one returned by the `get_hash_parameters` endpoint. It is not used by
+    /// the `none` algorithm.
+    ///
+    /// Returns `None` if the algorithm is not supported by this method.
+    ///
+    /// [spec]: https://spec.matrix.org/latest/identity-service-api/#client-behaviour
+    pub fn hash_address(&self, address: &str, medium: &Medium, pepper: &str) -> Option<String> {
+        match self {
+            Self::Sha256 => {
+                let hash = Sha256::digest(format!("{address} {} {pepper}", medium.as_str()));
+                Some(Base64::<UrlSafe, _>::new(hash.as_slice()).encode())
+            }
+            Self::None => Some(format!("{address} {}", medium.as_str())),
+            Self::_Custom(_) => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use ruma_common::thirdparty::Medium;
+
     use super::IdentifierHashingAlgorithm;
 
     #[test]
@@ -36,4 +63,28 @@ mod tests {
         assert_eq!(IdentifierHashingAlgorithm::from("sha256"), IdentifierHashingAlgorithm::Sha256);
         assert_eq!(IdentifierHashingAlgorithm::from("none"), IdentifierHashingAlgorithm::None);
     }
+
+    #[test]
+    fn hash_address() {
+        assert_eq!(
+            IdentifierHashingAlgorithm::Sha256
+                .hash_address("alice@example.com", &Medium::Email, "matrixrocks")
+                .unwrap(),
+            "4kenr7N9drpCJ4AfalmlGQVsOn3o2RHjkADUpXJWZUc"
+        );
+        assert_eq!(
+            IdentifierHashingAlgorithm::None
+                .hash_address("alice@example.com", &Medium::Email, "matrixrocks")
+                .unwrap(),
+            "alice@example.com email"
+        );
+        assert_eq!(
+            IdentifierHashingAlgorithm::from("sha512").hash_address(
+                "alice@example.com",
+                &Medium::Email,
+                "matrixrocks"
+            ),
+            None
+        );
+    }
 }
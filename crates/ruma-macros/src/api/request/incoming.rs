@@ -38,7 +38,9 @@ impl Request {
             let parse = quote! {
                 #( #cfg_attrs )*
                 let #field_name =
-                    #serde_html_form::from_str(&request.uri().query().unwrap_or(""))?;
+                    #serde_html_form::from_str(&#ruma_common::api::__private::normalize_query(
+                        request.uri().query().unwrap_or(""),
+                    ))?;
             };
 
             (
@@ -56,7 +58,9 @@ impl Request {
 
             let parse = quote! {
                 let request_query: RequestQuery =
-                    #serde_html_form::from_str(&request.uri().query().unwrap_or(""))?;
+                    #serde_html_form::from_str(&#ruma_common::api::__private::normalize_query(
+                        request.uri().query().unwrap_or(""),
+                    ))?;
 
                 #decls
             };
@@ -193,6 +197,11 @@ impl Request {
                     B: ::std::convert::AsRef<[::std::primitive::u8]>,
                     S: ::std::convert::AsRef<::std::primitive::str>,
                 {
+                    let _span = #ruma_common::api::__private::enter_conversion_span(
+                        "try_from_http_request",
+                        ::std::any::type_name::<Self>(),
+                    );
+
                     if !(request.method() == METADATA.method
                         || request.method() == #http::Method::HEAD
                             && METADATA.method == #http::Method::GET)
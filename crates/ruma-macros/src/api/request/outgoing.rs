@@ -114,6 +114,11 @@ impl Request {
                     access_token: #ruma_common::api::SendAccessToken<'_>,
                     considering_versions: &'_ [#ruma_common::api::MatrixVersion],
                 ) -> ::std::result::Result<#http::Request<T>, #ruma_common::api::error::IntoHttpError> {
+                    let _span = #ruma_common::api::__private::enter_conversion_span(
+                        "try_into_http_request",
+                        ::std::any::type_name::<Self>(),
+                    );
+
                     let mut req_builder = #http::Request::builder()
                         .method(METADATA.method)
                         .uri(METADATA.make_endpoint_url(
@@ -128,6 +128,11 @@ impl Response {
                     Self,
                     #ruma_common::api::error::FromHttpResponseError<#error_ty>,
                 > {
+                    let _span = #ruma_common::api::__private::enter_conversion_span(
+                        "try_from_http_response",
+                        ::std::any::type_name::<Self>(),
+                    );
+
                     if response.status().as_u16() < 400 {
                         #extract_response_headers
                         #typed_response_body_decl
@@ -68,6 +68,11 @@ impl Response {
                 fn try_into_http_response<T: ::std::default::Default + #bytes::BufMut>(
                     self,
                 ) -> ::std::result::Result<#http::Response<T>, #ruma_common::api::error::IntoHttpError> {
+                    let _span = #ruma_common::api::__private::enter_conversion_span(
+                        "try_into_http_response",
+                        ::std::any::type_name::<Self>(),
+                    );
+
                     let mut resp_builder = #http::Response::builder()
                         .status(#http::StatusCode::#status_ident)
                         .header(#http::header::CONTENT_TYPE, "application/json");
@@ -129,6 +129,11 @@ fn expand_deserialize_event(
                         #serde::de::IntoDeserializer::<A::Error>::into_deserializer(state_key),
                     )?;
                 }
+            } else if name == "origin_server_ts" {
+                quote! {
+                    let origin_server_ts =
+                        #ruma_events::origin_server_ts_or_default::<A::Error>(origin_server_ts)?;
+                }
             } else {
                 quote! {
                     let #name = #name.ok_or_else(|| {
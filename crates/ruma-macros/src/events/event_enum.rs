@@ -697,7 +697,7 @@ fn expand_accessor_methods(
     });
 
     let maybe_redacted_accessors = maybe_redacted.then(|| {
-        let variants = variants.iter().map(|v| v.match_arm(quote! { Self }));
+        let variants: Vec<_> = variants.iter().map(|v| v.match_arm(quote! { Self })).collect();
 
         quote! {
             /// Returns this event's `transaction_id` from inside `unsigned`, if there is one.
@@ -713,6 +713,30 @@ fn expand_accessor_methods(
                     }
                 }
             }
+
+            /// Whether this event is redacted.
+            pub fn is_redacted(&self) -> bool {
+                match self {
+                    #( #variants(event) => event.is_redacted(), )*
+                    Self::_Custom(event) => event.is_redacted(),
+                }
+            }
+
+            /// Returns this event's `redacted_because` from inside `unsigned`, if it is redacted.
+            pub fn redacted_because(&self) -> Option<&#ruma_events::UnsignedRoomRedactionEvent> {
+                match self {
+                    #( #variants(event) => event.redacted_because(), )*
+                    Self::_Custom(event) => event.redacted_because(),
+                }
+            }
+
+            /// Returns the `reason` of this event's redaction, if it is redacted and has one.
+            pub fn redaction_reason(&self) -> Option<&::std::primitive::str> {
+                match self {
+                    #( #variants(event) => event.redaction_reason(), )*
+                    Self::_Custom(event) => event.redaction_reason(),
+                }
+            }
         }
     });
 
@@ -131,6 +131,35 @@ fn generate_enum(
         })
         .collect::<syn::Result<_>>()?;
 
+    let stable_name_match_arms: Vec<_> = deduped
+        .iter()
+        .map(|e| {
+            let v = e.to_variant()?;
+            let start = v.match_arm(quote! { Self });
+            let stable_type =
+                [&e.ev_type].into_iter().chain(&e.aliases).find(|t| t.value().starts_with("m."));
+
+            Ok(match stable_type {
+                Some(ev_type) => {
+                    if let Some(prefix) = ev_type.value().strip_suffix(".*") {
+                        let fstr = prefix.to_owned() + ".{}";
+                        quote! {
+                            #start(_s) => {
+                                Some(::std::borrow::Cow::Owned(::std::format!(#fstr, _s)))
+                            }
+                        }
+                    } else if e.has_type_fragment() {
+                        quote! { #start(_) => Some(::std::borrow::Cow::Borrowed(#ev_type)) }
+                    } else {
+                        quote! { #start => Some(::std::borrow::Cow::Borrowed(#ev_type)) }
+                    }
+                }
+                None if e.has_type_fragment() => quote! { #start(_) => None },
+                None => quote! { #start => None },
+            })
+        })
+        .collect::<syn::Result<_>>()?;
+
     let mut from_str_match_arms = TokenStream::new();
     for event in &deduped {
         let v = event.to_variant()?;
@@ -214,6 +243,25 @@ fn generate_enum(
                     Self::_Custom(crate::PrivOwnedStr(s)) => ::std::borrow::Cow::Borrowed(s),
                 }
             }
+
+            /// The stable name of this event type, if it has one.
+            ///
+            /// Event types that are still being specified are serialized with their unstable name,
+            /// but they are also deserialized from the `m.`-prefixed name they will have once they
+            /// are stabilized. This returns that stable name, which can be used when the other side
+            /// is known to support it. The serialization of this type always uses the unstable
+            /// name, so this choice is left to the caller.
+            ///
+            /// For event types that are already stable, this returns the same string as the
+            /// `Display` implementation.
+            pub fn stable_name(&self) -> Option<::std::borrow::Cow<'_, ::std::primitive::str>> {
+                match self {
+                    #(#stable_name_match_arms,)*
+                    Self::_Custom(crate::PrivOwnedStr(s)) => {
+                        s.starts_with("m.").then(|| ::std::borrow::Cow::Borrowed(&**s))
+                    }
+                }
+            }
         }
 
         #[allow(deprecated)]
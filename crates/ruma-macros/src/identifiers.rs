@@ -559,6 +559,23 @@ fn expand_checked_impls(input: &ItemStruct, validate: Path) -> TokenStream {
             }
         }
 
+        #[automatically_derived]
+        impl<'de: 'a, 'a, #generic_params> serde::Deserialize<'de> for &'a #id_ty {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                use serde::de::Error;
+
+                let s = <&'a str>::deserialize(deserializer)?;
+
+                match <&'a #id_ty>::try_from(s) {
+                    Ok(o) => Ok(o),
+                    Err(e) => Err(D::Error::custom(e)),
+                }
+            }
+        }
+
         #[automatically_derived]
         impl<'a, #generic_params> std::convert::TryFrom<&'a str> for &'a #id_ty {
             type Error = crate::IdParseError;
@@ -686,6 +703,16 @@ fn expand_unchecked_impls(input: &ItemStruct) -> TokenStream {
             }
         }
 
+        #[automatically_derived]
+        impl<'de: 'a, 'a> serde::Deserialize<'de> for &'a #id {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                <&'a str>::deserialize(deserializer).map(#id::from_borrowed)
+            }
+        }
+
         #[automatically_derived]
         impl<'de> serde::Deserialize<'de> for Box<#id> {
             fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -216,7 +216,7 @@ pub mod v1 {
         /// A dictionary of customisations made to the way this notification is to be presented.
         ///
         /// These are added by push rules.
-        #[serde(with = "tweak_serde", skip_serializing_if = "Vec::is_empty")]
+        #[serde(default, with = "tweak_serde", skip_serializing_if = "Vec::is_empty")]
         pub tweaks: Vec<Tweak>,
     }
 
@@ -304,6 +304,7 @@ pub mod v1 {
             ser::SerializeMap,
             Deserializer, Serializer,
         };
+        use serde_json::value::RawValue as RawJsonValue;
 
         pub(super) fn serialize<S>(tweak: &[Tweak], serializer: S) -> Result<S::Ok, S::Error>
         where
@@ -337,17 +338,21 @@ pub mod v1 {
             {
                 let mut tweaks = vec![];
                 while let Some(key) = access.next_key::<String>()? {
-                    match &*key {
-                        "sound" => tweaks.push(Tweak::Sound(access.next_value()?)),
+                    let value = access.next_value::<Box<RawJsonValue>>()?;
+
+                    // Known tweaks with an invalid value are kept as custom tweaks, to avoid
+                    // losing data.
+                    let tweak = match &*key {
+                        "sound" => serde_json::from_str(value.get()).ok().map(Tweak::Sound),
                         // If a highlight tweak is given with no value, its value is defined to be
                         // true.
-                        "highlight" => {
-                            let highlight = access.next_value().unwrap_or(true);
-
-                            tweaks.push(Tweak::Highlight(highlight));
-                        }
-                        _ => tweaks.push(Tweak::Custom { name: key, value: access.next_value()? }),
+                        "highlight" => serde_json::from_str::<Option<bool>>(value.get())
+                            .ok()
+                            .map(|highlight| Tweak::Highlight(highlight.unwrap_or(true))),
+                        _ => None,
                     };
+
+                    tweaks.push(tweak.unwrap_or(Tweak::Custom { name: key, value: Some(value) }));
                 }
 
                 // If no highlight tweak is given at all then the value of highlight is defined to
@@ -424,7 +429,7 @@ pub mod v1 {
                     Tweak::Sound("silence".into()),
                     Tweak::Custom {
                         name: "custom".into(),
-                        value: from_json_value(JsonValue::String("go wild".into())).unwrap(),
+                        value: Some(from_json_value(JsonValue::String("go wild".into())).unwrap()),
                     },
                 ],
                 ..Device::new(
@@ -450,5 +455,60 @@ pub mod v1 {
 
             assert_eq!(expected, to_json_value(notice).unwrap());
         }
+
+        #[test]
+        fn deserialize_device_without_tweaks() {
+            let device = from_json_value::<Device>(json!({
+                "app_id": "org.matrix.matrixConsole.ios",
+                "pushkey": "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/",
+            }))
+            .unwrap();
+
+            assert_eq!(device.app_id, "org.matrix.matrixConsole.ios");
+            assert!(device.tweaks.is_empty());
+        }
+
+        #[test]
+        fn deserialize_device_tweaks() {
+            let device = from_json_value::<Device>(json!({
+                "app_id": "org.matrix.matrixConsole.ios",
+                "pushkey": "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/",
+                "tweaks": {
+                    "sound": "default",
+                    "highlight": null,
+                    "custom": "go wild",
+                },
+            }))
+            .unwrap();
+
+            assert_eq!(device.tweaks.len(), 3);
+            assert!(device.tweaks.iter().any(|t| matches!(t, Tweak::Sound(s) if s == "default")));
+            assert!(device.tweaks.iter().any(|t| matches!(t, Tweak::Highlight(true))));
+            assert!(device.tweaks.iter().any(|t| matches!(
+                t,
+                Tweak::Custom { name, value: Some(value) }
+                    if name == "custom" && value.get() == r#""go wild""#
+            )));
+        }
+
+        #[test]
+        fn deserialize_device_tweaks_with_invalid_value() {
+            let device = from_json_value::<Device>(json!({
+                "app_id": "org.matrix.matrixConsole.ios",
+                "pushkey": "V2h5IG9uIGVhcnRoIGRpZCB5b3UgZGVjb2RlIHRoaXM/",
+                "tweaks": {
+                    "sound": true,
+                    "highlight": false,
+                },
+            }))
+            .unwrap();
+
+            assert_eq!(device.tweaks.len(), 2);
+            assert!(device.tweaks.iter().any(|t| matches!(
+                t,
+                Tweak::Custom { name, value: Some(value) } if name == "sound" && value.get() == "true"
+            )));
+            assert!(device.tweaks.iter().any(|t| matches!(t, Tweak::Highlight(false))));
+        }
     }
 }
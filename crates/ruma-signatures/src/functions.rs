@@ -205,6 +205,15 @@ pub fn canonical_json(object: &CanonicalJsonObject) -> Result<String, Error> {
 /// // Verify at least one signature for each entity in `public_key_map`.
 /// assert!(ruma_signatures::verify_json(&public_key_map, &object).is_ok());
 /// ```
+#[cfg_attr(
+    feature = "tracing-spans",
+    tracing::instrument(
+        name = "ruma_signatures",
+        level = "debug",
+        skip_all,
+        fields(ruma.operation = "verify_json"),
+    )
+)]
 pub fn verify_json(
     public_key_map: &PublicKeyMap,
     object: &CanonicalJsonObject,
@@ -539,6 +548,15 @@ where
 /// assert!(verification_result.is_ok());
 /// assert_eq!(verification_result.unwrap(), Verified::All);
 /// ```
+#[cfg_attr(
+    feature = "tracing-spans",
+    tracing::instrument(
+        name = "ruma_signatures",
+        level = "debug",
+        skip_all,
+        fields(ruma.operation = "verify_event", ruma.room_version = %version),
+    )
+)]
 pub fn verify_event(
     public_key_map: &PublicKeyMap,
     object: &CanonicalJsonObject,
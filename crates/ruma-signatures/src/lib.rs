@@ -44,7 +44,10 @@
 
 #![warn(missing_docs)]
 
-use ruma_common::serde::{AsRefStr, DisplayAsRefStr};
+use ruma_common::{
+    serde::{AsRefStr, DisplayAsRefStr},
+    SigningKeyAlgorithm,
+};
 
 pub use self::{
     error::{Error, JsonError, ParseError, VerificationError},
@@ -61,6 +64,8 @@ mod error;
 mod functions;
 mod keys;
 mod signatures;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 mod verification;
 
 /// The algorithm used for signing data.
@@ -72,6 +77,14 @@ pub enum Algorithm {
     Ed25519,
 }
 
+impl From<Algorithm> for SigningKeyAlgorithm {
+    fn from(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::Ed25519 => Self::Ed25519,
+        }
+    }
+}
+
 /// Extract the algorithm and version from a key identifier.
 fn split_id(id: &str) -> Result<(Algorithm, String), Error> {
     /// The length of a valid signature ID.
@@ -97,9 +110,9 @@ fn split_id(id: &str) -> Result<(Algorithm, String), Error> {
 
     let algorithm_input = signature_id[0];
 
-    let algorithm = match algorithm_input {
-        "ed25519" => Algorithm::Ed25519,
-        algorithm => return Err(Error::UnsupportedAlgorithm(algorithm.into())),
+    let algorithm = match SigningKeyAlgorithm::from(algorithm_input) {
+        SigningKeyAlgorithm::Ed25519 => Algorithm::Ed25519,
+        _ => return Err(Error::UnsupportedAlgorithm(algorithm_input.into())),
     };
 
     Ok((algorithm, signature_id[1].to_owned()))
@@ -0,0 +1,260 @@
+//! Helpers to build signed events for tests.
+//!
+//! These are meant to replace hand-written JSON fixtures of PDUs. The events built here have
+//! valid content hashes and signatures, using throwaway keys that are generated on the fly.
+
+use std::collections::BTreeMap;
+
+use js_int::UInt;
+use ruma_common::{
+    serde::Base64, CanonicalJsonObject, CanonicalJsonValue, EventId, MilliSecondsSinceUnixEpoch,
+    OwnedEventId, OwnedRoomId, OwnedServerName, OwnedUserId, RoomVersionId, ServerName,
+};
+use serde_json::Value as JsonValue;
+
+use crate::{hash_and_sign_event, reference_hash, Ed25519KeyPair, PublicKeyMap};
+
+/// A throwaway signing key for a homeserver.
+#[derive(Debug)]
+pub struct TestServerKey {
+    server_name: OwnedServerName,
+    key_pair: Ed25519KeyPair,
+}
+
+impl TestServerKey {
+    /// Generates a new Ed25519 key for the given server, with the version `1`.
+    pub fn generate(server_name: &ServerName) -> Self {
+        let document = Ed25519KeyPair::generate().expect("generating a key pair should succeed");
+        let key_pair = Ed25519KeyPair::from_der(&document, "1".to_owned())
+            .expect("a generated key pair should be valid");
+
+        Self { server_name: server_name.to_owned(), key_pair }
+    }
+
+    /// The name of the server this key belongs to.
+    pub fn server_name(&self) -> &ServerName {
+        &self.server_name
+    }
+
+    /// The key pair of this key.
+    pub fn key_pair(&self) -> &Ed25519KeyPair {
+        &self.key_pair
+    }
+
+    /// A public key map containing only this key, to verify the events it signed.
+    pub fn public_key_map(&self) -> PublicKeyMap {
+        let key_id = format!("ed25519:{}", self.key_pair.version());
+        let public_key = Base64::new(self.key_pair.public_key().to_vec());
+
+        BTreeMap::from([(self.server_name.to_string(), BTreeMap::from([(key_id, public_key)]))])
+    }
+}
+
+/// A builder for a signed PDU.
+///
+/// The event is hashed and signed by the [`TestServerKey`] passed to [`PduBuilder::build()`], and
+/// its event ID is computed according to the format of the room version.
+#[derive(Clone, Debug)]
+pub struct PduBuilder {
+    room_version: RoomVersionId,
+    room_id: OwnedRoomId,
+    sender: OwnedUserId,
+    event_type: String,
+    state_key: Option<String>,
+    content: JsonValue,
+    prev_events: Vec<OwnedEventId>,
+    auth_events: Vec<OwnedEventId>,
+    depth: UInt,
+    origin_server_ts: MilliSecondsSinceUnixEpoch,
+}
+
+impl PduBuilder {
+    /// Creates a new `PduBuilder` for a message-like event with the given room version, room ID,
+    /// sender, type and content.
+    ///
+    /// The event has a depth of `1`, no previous or auth events and the current time as its
+    /// timestamp.
+    pub fn new(
+        room_version: RoomVersionId,
+        room_id: OwnedRoomId,
+        sender: OwnedUserId,
+        event_type: impl Into<String>,
+        content: JsonValue,
+    ) -> Self {
+        Self {
+            room_version,
+            room_id,
+            sender,
+            event_type: event_type.into(),
+            state_key: None,
+            content,
+            prev_events: Vec::new(),
+            auth_events: Vec::new(),
+            depth: UInt::from(1_u32),
+            origin_server_ts: MilliSecondsSinceUnixEpoch::now(),
+        }
+    }
+
+    /// Sets the state key of the event, making it a state event.
+    pub fn state_key(mut self, state_key: impl Into<String>) -> Self {
+        self.state_key = Some(state_key.into());
+        self
+    }
+
+    /// Sets the IDs of the previous events of the event.
+    pub fn prev_events(mut self, prev_events: Vec<OwnedEventId>) -> Self {
+        self.prev_events = prev_events;
+        self
+    }
+
+    /// Sets the IDs of the auth events of the event.
+    pub fn auth_events(mut self, auth_events: Vec<OwnedEventId>) -> Self {
+        self.auth_events = auth_events;
+        self
+    }
+
+    /// Sets the depth of the event.
+    pub fn depth(mut self, depth: UInt) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the timestamp of the event.
+    pub fn origin_server_ts(mut self, origin_server_ts: MilliSecondsSinceUnixEpoch) -> Self {
+        self.origin_server_ts = origin_server_ts;
+        self
+    }
+
+    /// Hashes and signs the event with the given key, and returns its ID and its JSON.
+    ///
+    /// In room versions 1 and 2, the event ID is generated randomly and is included in the JSON,
+    /// and the references to previous and auth events have no hashes. In later room versions, the
+    /// event ID is the reference hash of the event.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the content is not a JSON object that is valid canonical JSON, or if the event is
+    /// too large.
+    pub fn build(self, key: &TestServerKey) -> (OwnedEventId, CanonicalJsonObject) {
+        let uses_event_id_field =
+            matches!(self.room_version, RoomVersionId::V1 | RoomVersionId::V2);
+
+        let content = match CanonicalJsonValue::try_from(self.content) {
+            Ok(content @ CanonicalJsonValue::Object(_)) => content,
+            _ => panic!("event content should be a canonical JSON object"),
+        };
+
+        let event_refs = |ids: Vec<OwnedEventId>| {
+            let refs = ids
+                .into_iter()
+                .map(|id| {
+                    if uses_event_id_field {
+                        CanonicalJsonValue::Array(vec![
+                            CanonicalJsonValue::String(id.into()),
+                            CanonicalJsonValue::Object(BTreeMap::new()),
+                        ])
+                    } else {
+                        CanonicalJsonValue::String(id.into())
+                    }
+                })
+                .collect();
+            CanonicalJsonValue::Array(refs)
+        };
+
+        let mut object = CanonicalJsonObject::from([
+            ("auth_events".to_owned(), event_refs(self.auth_events)),
+            ("content".to_owned(), content),
+            ("depth".to_owned(), self.depth.into()),
+            ("origin".to_owned(), CanonicalJsonValue::String(key.server_name.to_string())),
+            ("origin_server_ts".to_owned(), self.origin_server_ts.0.into()),
+            ("prev_events".to_owned(), event_refs(self.prev_events)),
+            ("room_id".to_owned(), CanonicalJsonValue::String(self.room_id.into())),
+            ("sender".to_owned(), CanonicalJsonValue::String(self.sender.into())),
+            ("type".to_owned(), CanonicalJsonValue::String(self.event_type)),
+        ]);
+
+        if let Some(state_key) = self.state_key {
+            object.insert("state_key".to_owned(), CanonicalJsonValue::String(state_key));
+        }
+
+        let event_id = if uses_event_id_field {
+            let event_id = EventId::new(&key.server_name);
+            object.insert("event_id".to_owned(), CanonicalJsonValue::String(event_id.to_string()));
+            Some(event_id)
+        } else {
+            None
+        };
+
+        hash_and_sign_event(
+            key.server_name.as_str(),
+            &key.key_pair,
+            &mut object,
+            &self.room_version,
+        )
+        .expect("hashing and signing the event should succeed");
+
+        let event_id = event_id.unwrap_or_else(|| {
+            let hash = reference_hash(&object, &self.room_version)
+                .expect("computing the reference hash of the event should succeed");
+            EventId::parse(format!("${hash}"))
+                .expect("the reference hash should be a valid event ID")
+        });
+
+        (event_id, object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::{owned_room_id, owned_user_id, server_name, RoomVersionId};
+    use serde_json::json;
+
+    use super::{PduBuilder, TestServerKey};
+    use crate::{reference_hash, verify_event, Verified};
+
+    #[test]
+    fn build_verifiable_pdus() {
+        let key = TestServerKey::generate(server_name!("example.org"));
+
+        for version in [RoomVersionId::V1, RoomVersionId::V6, RoomVersionId::V11] {
+            let (create_id, create) = PduBuilder::new(
+                version.clone(),
+                owned_room_id!("!room:example.org"),
+                owned_user_id!("@alice:example.org"),
+                "m.room.create",
+                json!({ "creator": "@alice:example.org", "room_version": version }),
+            )
+            .state_key("")
+            .build(&key);
+
+            assert_eq!(
+                verify_event(&key.public_key_map(), &create, &version).unwrap(),
+                Verified::All
+            );
+
+            let (_, message) = PduBuilder::new(
+                version.clone(),
+                owned_room_id!("!room:example.org"),
+                owned_user_id!("@alice:example.org"),
+                "m.room.message",
+                json!({ "msgtype": "m.text", "body": "Hello" }),
+            )
+            .prev_events(vec![create_id.clone()])
+            .auth_events(vec![create_id.clone()])
+            .build(&key);
+
+            assert_eq!(
+                verify_event(&key.public_key_map(), &message, &version).unwrap(),
+                Verified::All
+            );
+
+            if version == RoomVersionId::V1 {
+                assert_eq!(create.get("event_id").unwrap().as_str(), Some(create_id.as_str()));
+            } else {
+                assert!(!create.contains_key("event_id"));
+                let hash = reference_hash(&create, &version).unwrap();
+                assert_eq!(create_id.as_str(), format!("${hash}"));
+            }
+        }
+    }
+}
@@ -3,11 +3,11 @@ use std::{borrow::Borrow, collections::BTreeSet};
 use js_int::{int, Int};
 use ruma_common::{
     serde::{Base64, Raw},
-    OwnedUserId, RoomVersionId, UserId,
+    OwnedRoomId, OwnedUserId, RoomId, RoomVersionId, UserId,
 };
 use ruma_events::room::{
     create::RoomCreateEventContent,
-    join_rules::{JoinRule, RoomJoinRulesEventContent},
+    join_rules::{AllowRule, JoinRule, RoomJoinRulesEventContent},
     member::{MembershipState, ThirdPartyInvite},
     power_levels::RoomPowerLevelsEventContent,
     third_party_invite::RoomThirdPartyInviteEventContent,
@@ -82,12 +82,15 @@ pub fn auth_types_for_event(
                         auth_types.push(key);
                     }
 
-                    if let Some(Ok(u)) =
-                        content.join_authorised_via_users_server.map(|m| m.deserialize())
-                    {
-                        let key = (StateEventType::RoomMember, u.to_string());
-                        if !auth_types.contains(&key) {
-                            auth_types.push(key);
+                    // The user authorising a join is only relevant for restricted joins.
+                    if membership == MembershipState::Join {
+                        if let Some(Ok(u)) =
+                            content.join_authorised_via_users_server.map(|m| m.deserialize())
+                        {
+                            let key = (StateEventType::RoomMember, u.to_string());
+                            if !auth_types.contains(&key) {
+                                auth_types.push(key);
+                            }
                         }
                     }
                 }
@@ -694,9 +697,9 @@ fn valid_membership_change(
         }
         MembershipState::Knock if room_version.allow_knocking => {
             // 1. If the `join_rule` is anything other than `knock` or `knock_restricted`, reject.
-            if join_rules != JoinRule::Knock
+            if !(join_rules == JoinRule::Knock
                 || room_version.knock_restricted_join_rule
-                    && matches!(join_rules, JoinRule::KnockRestricted(_))
+                    && matches!(join_rules, JoinRule::KnockRestricted(_)))
             {
                 warn!("Join rule is not set to knock or knock_restricted, knocking is not allowed");
                 false
@@ -730,6 +733,160 @@ fn valid_membership_change(
     })
 }
 
+/// The outcome of [`check_restricted_join()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum RestrictedJoin {
+    /// The join rule of the room is not `restricted` or `knock_restricted`, or the room version
+    /// doesn't support it.
+    NotRestricted,
+
+    /// The user is banned from the room, or is not a member of any of the rooms allowed by the
+    /// join rule.
+    NotAllowed,
+
+    /// The user can join the room because they are a member of the given room.
+    Allowed {
+        /// The allowed room that the user is a member of.
+        via_room: OwnedRoomId,
+    },
+}
+
+/// Check whether the given user can join a room with a restricted join rule without an invite.
+///
+/// This implements the `restricted` ([MSC3083]) and `knock_restricted` ([MSC3787]) join rules, for
+/// a resident server that receives a join request. If the join is allowed, the join event must
+/// still be authorised by a user that passes [`can_authorize_restricted_join()`].
+///
+/// Users that are already invited or joined don't need the join rule to join the room, so this
+/// should only be called for other users.
+///
+/// The `fetch_state` closure should gather state from the current state of the room, and
+/// `is_joined_to` should return whether the user is joined to the given room.
+///
+/// [MSC3083]: https://github.com/matrix-org/matrix-spec-proposals/pull/3083
+/// [MSC3787]: https://github.com/matrix-org/matrix-spec-proposals/pull/3787
+pub fn check_restricted_join<E: Event>(
+    room_version: &RoomVersion,
+    user_id: &UserId,
+    fetch_state: impl Fn(&StateEventType, &str) -> Option<E>,
+    is_joined_to: impl Fn(&RoomId) -> bool,
+) -> Result<RestrictedJoin> {
+    let allow = match current_join_rule(&fetch_state)? {
+        JoinRule::Restricted(restricted) if room_version.restricted_join_rules => restricted.allow,
+        JoinRule::KnockRestricted(restricted) if room_version.knock_restricted_join_rule => {
+            restricted.allow
+        }
+        _ => return Ok(RestrictedJoin::NotRestricted),
+    };
+
+    if current_membership(&fetch_state, user_id)? == MembershipState::Ban {
+        return Ok(RestrictedJoin::NotAllowed);
+    }
+
+    let via_room = allow.into_iter().find_map(|rule| match rule {
+        AllowRule::RoomMembership(membership) if is_joined_to(&membership.room_id) => {
+            Some(membership.room_id)
+        }
+        _ => None,
+    });
+
+    Ok(via_room.map_or(RestrictedJoin::NotAllowed, |via_room| RestrictedJoin::Allowed { via_room }))
+}
+
+/// Check whether the given user can authorise a join to a room with a restricted join rule, by
+/// being set as the `join_authorised_via_users_server` of the join event.
+///
+/// The user must be joined to the room and have the power level to invite other users.
+///
+/// The `fetch_state` closure should gather state from the current state of the room.
+pub fn can_authorize_restricted_join<E: Event>(
+    room_version: &RoomVersion,
+    user_id: &UserId,
+    fetch_state: impl Fn(&StateEventType, &str) -> Option<E>,
+) -> Result<bool> {
+    user_can_invite(room_version, user_id, &fetch_state)
+}
+
+/// Check whether the given sender can accept the knock of the target user, by inviting them to
+/// the room.
+///
+/// This implements the `knock` ([MSC2403]) and `knock_restricted` ([MSC3787]) join rules. The
+/// target user must currently have knocked on the room, and the sender must be joined to the room
+/// and have the power level to invite other users.
+///
+/// The `fetch_state` closure should gather state from the current state of the room.
+///
+/// [MSC2403]: https://github.com/matrix-org/matrix-spec-proposals/pull/2403
+/// [MSC3787]: https://github.com/matrix-org/matrix-spec-proposals/pull/3787
+pub fn can_accept_knock<E: Event>(
+    room_version: &RoomVersion,
+    sender: &UserId,
+    target_user: &UserId,
+    fetch_state: impl Fn(&StateEventType, &str) -> Option<E>,
+) -> Result<bool> {
+    if !room_version.allow_knocking {
+        return Ok(false);
+    }
+
+    let allows_knocking = match current_join_rule(&fetch_state)? {
+        JoinRule::Knock => true,
+        JoinRule::KnockRestricted(_) => room_version.knock_restricted_join_rule,
+        _ => false,
+    };
+
+    Ok(allows_knocking
+        && current_membership(&fetch_state, target_user)? == MembershipState::Knock
+        && user_can_invite(room_version, sender, &fetch_state)?)
+}
+
+/// Whether the given user is joined to the room and has the power level to invite other users.
+fn user_can_invite<E: Event>(
+    room_version: &RoomVersion,
+    user_id: &UserId,
+    fetch_state: &impl Fn(&StateEventType, &str) -> Option<E>,
+) -> Result<bool> {
+    if current_membership(fetch_state, user_id)? != MembershipState::Join {
+        return Ok(false);
+    }
+
+    let (user_level, invite_level) = match fetch_state(&StateEventType::RoomPowerLevels, "") {
+        Some(pl) => {
+            let invite =
+                deserialize_power_levels_content_invite(pl.content().get(), room_version)?.invite;
+            let content =
+                deserialize_power_levels_content_fields(pl.content().get(), room_version)?;
+            let user_level = content.users.get(user_id).copied().unwrap_or(content.users_default);
+
+            (user_level, invite)
+        }
+        None => (int!(0), int!(0)),
+    };
+
+    Ok(user_level >= invite_level)
+}
+
+/// The current join rule of the room, `invite` if there is none.
+fn current_join_rule<E: Event>(
+    fetch_state: &impl Fn(&StateEventType, &str) -> Option<E>,
+) -> Result<JoinRule> {
+    Ok(match fetch_state(&StateEventType::RoomJoinRules, "") {
+        Some(ev) => from_json_str::<RoomJoinRulesEventContent>(ev.content().get())?.join_rule,
+        None => JoinRule::Invite,
+    })
+}
+
+/// The current membership of the given user in the room, `leave` if there is none.
+fn current_membership<E: Event>(
+    fetch_state: &impl Fn(&StateEventType, &str) -> Option<E>,
+    user_id: &UserId,
+) -> Result<MembershipState> {
+    Ok(match fetch_state(&StateEventType::RoomMember, user_id.as_str()) {
+        Some(ev) => from_json_str::<GetMembership>(ev.content().get())?.membership,
+        None => MembershipState::Leave,
+    })
+}
+
 /// Is the user allowed to send a specific event based on the rooms power levels.
 ///
 /// Does the event have the correct userId as its state_key if it's not the "" state_key.
@@ -1010,6 +1167,7 @@ fn verify_third_party_invite(
 mod tests {
     use std::sync::Arc;
 
+    use ruma_common::room_id;
     use ruma_events::{
         room::{
             join_rules::{
@@ -1019,12 +1177,16 @@ mod tests {
         },
         StateEventType, TimelineEventType,
     };
-    use serde_json::value::to_raw_value as to_raw_json_value;
+    use serde_json::{json, value::to_raw_value as to_raw_json_value};
 
+    use super::{
+        auth_types_for_event, can_accept_knock, can_authorize_restricted_join,
+        check_restricted_join, RestrictedJoin,
+    };
     use crate::{
         event_auth::valid_membership_change,
         test_utils::{
-            alice, charlie, ella, event_id, member_content_ban, member_content_join, room_id,
+            alice, bob, charlie, ella, event_id, member_content_ban, member_content_join, room_id,
             to_pdu_event, PduEvent, INITIAL_EVENTS, INITIAL_EVENTS_CREATE_ROOM,
         },
         Event, EventTypeExt, RoomVersion, StateMap,
@@ -1323,4 +1485,123 @@ mod tests {
         )
         .unwrap());
     }
+
+    #[test]
+    fn test_check_restricted_join() {
+        let space_id = room_id!("!space:foo");
+        let mut events = INITIAL_EVENTS();
+        *events.get_mut(&event_id("IPOWER")).unwrap() = to_pdu_event(
+            "IPOWER",
+            alice(),
+            TimelineEventType::RoomPowerLevels,
+            Some(""),
+            to_raw_json_value(&json!({ "users": { alice(): 100 }, "invite": 50 })).unwrap(),
+            &["CREATE", "IMA"],
+            &["IMA"],
+        );
+        *events.get_mut(&event_id("IJR")).unwrap() = to_pdu_event(
+            "IJR",
+            alice(),
+            TimelineEventType::RoomJoinRules,
+            Some(""),
+            to_raw_json_value(&RoomJoinRulesEventContent::restricted(vec![
+                AllowRule::room_membership(space_id.to_owned()),
+            ]))
+            .unwrap(),
+            &["CREATE", "IMA", "IPOWER"],
+            &["IPOWER"],
+        );
+
+        let state = events
+            .values()
+            .map(|ev| (ev.event_type().with_state_key(ev.state_key().unwrap()), Arc::clone(ev)))
+            .collect::<StateMap<_>>();
+        let fetch_state =
+            |ty: &StateEventType, key: &str| state.get(&(ty.clone(), key.to_owned())).cloned();
+
+        assert_eq!(
+            check_restricted_join(&RoomVersion::V9, ella(), fetch_state, |r| r == space_id)
+                .unwrap(),
+            RestrictedJoin::Allowed { via_room: space_id.to_owned() }
+        );
+        assert_eq!(
+            check_restricted_join(&RoomVersion::V9, ella(), fetch_state, |_| false).unwrap(),
+            RestrictedJoin::NotAllowed
+        );
+        assert_eq!(
+            check_restricted_join(&RoomVersion::V7, ella(), fetch_state, |r| r == space_id)
+                .unwrap(),
+            RestrictedJoin::NotRestricted
+        );
+
+        assert!(can_authorize_restricted_join(&RoomVersion::V9, alice(), fetch_state).unwrap());
+        // Not enough power to invite.
+        assert!(!can_authorize_restricted_join(&RoomVersion::V9, bob(), fetch_state).unwrap());
+        // Not joined.
+        assert!(!can_authorize_restricted_join(&RoomVersion::V9, ella(), fetch_state).unwrap());
+    }
+
+    #[test]
+    fn test_can_accept_knock() {
+        let mut events = INITIAL_EVENTS();
+        *events.get_mut(&event_id("IJR")).unwrap() = to_pdu_event(
+            "IJR",
+            alice(),
+            TimelineEventType::RoomJoinRules,
+            Some(""),
+            to_raw_json_value(&RoomJoinRulesEventContent::knock_restricted(vec![])).unwrap(),
+            &["CREATE", "IMA", "IPOWER"],
+            &["IPOWER"],
+        );
+        let knock = to_pdu_event(
+            "IME",
+            ella(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            to_raw_json_value(&RoomMemberEventContent::new(MembershipState::Knock)).unwrap(),
+            &["CREATE", "IJR", "IPOWER"],
+            &["IMC"],
+        );
+        events.insert(knock.event_id().to_owned(), knock);
+
+        let state = events
+            .values()
+            .map(|ev| (ev.event_type().with_state_key(ev.state_key().unwrap()), Arc::clone(ev)))
+            .collect::<StateMap<_>>();
+        let fetch_state =
+            |ty: &StateEventType, key: &str| state.get(&(ty.clone(), key.to_owned())).cloned();
+
+        assert!(can_accept_knock(&RoomVersion::V10, alice(), ella(), fetch_state).unwrap());
+        // The room version doesn't support the `knock_restricted` join rule.
+        assert!(!can_accept_knock(&RoomVersion::V9, alice(), ella(), fetch_state).unwrap());
+        // The target user didn't knock.
+        assert!(!can_accept_knock(&RoomVersion::V10, alice(), charlie(), fetch_state).unwrap());
+        // The sender is not joined.
+        assert!(!can_accept_knock(&RoomVersion::V10, ella(), ella(), fetch_state).unwrap());
+    }
+
+    #[test]
+    fn test_auth_types_for_join_authorised_via_users_server() {
+        let mut content = RoomMemberEventContent::new(MembershipState::Join);
+        content.join_authorized_via_users_server = Some(alice().to_owned());
+
+        let auth_types = auth_types_for_event(
+            &TimelineEventType::RoomMember,
+            ella(),
+            Some(ella().as_str()),
+            &to_raw_json_value(&content).unwrap(),
+        )
+        .unwrap();
+        assert!(auth_types.contains(&(StateEventType::RoomMember, alice().to_string())));
+
+        content.membership = MembershipState::Knock;
+        let auth_types = auth_types_for_event(
+            &TimelineEventType::RoomMember,
+            ella(),
+            Some(ella().as_str()),
+            &to_raw_json_value(&content).unwrap(),
+        )
+        .unwrap();
+        assert!(!auth_types.contains(&(StateEventType::RoomMember, alice().to_string())));
+    }
 }
@@ -52,7 +52,8 @@
 //!   breaking changes when new fields are added in the specification. This feature compiles all
 //!   types as exhaustive.
 //! * `unstable-mscXXXX`, where `XXXX` is the MSC number -- Upcoming Matrix features that may be
-//!   subject to change or removal.
+//!   subject to change or removal. The enabled features can be listed at runtime with
+//!   [`unstable_features()`].
 //! * `unstable-unspecified` -- Undocumented Matrix features that may be subject to change or
 //!   removal.
 //!
@@ -77,6 +78,8 @@
 #![warn(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
+mod unstable_features;
+
 #[cfg(feature = "client")]
 #[doc(inline)]
 pub use ruma_client as client;
@@ -133,3 +136,5 @@ pub use js_option::JsOption;
 pub use ruma_client::Client;
 pub use ruma_common::*;
 pub use web_time as time;
+
+pub use self::unstable_features::unstable_features;
@@ -0,0 +1,107 @@
+macro_rules! unstable_features {
+    ($( $feature:literal, )*) => {
+        /// The unstable features that are enabled in this build of Ruma.
+        ///
+        /// Each item is the name of an enabled `unstable-mscXXXX` cargo feature, where `XXXX` is
+        /// the number of the Matrix Spec Change whose (de)serialization is supported, like
+        /// `unstable-msc3575`. Some features have an additional suffix, for compatibility with
+        /// older iterations of the same MSC.
+        ///
+        /// This can be used by applications to know which unstable features they can advertise to
+        /// servers or users.
+        pub fn unstable_features() -> impl Iterator<Item = &'static str> {
+            [$( ($feature, cfg!(feature = $feature)), )*]
+                .into_iter()
+                .filter_map(|(feature, enabled)| enabled.then_some(feature))
+        }
+
+        #[cfg(test)]
+        const ALL_UNSTABLE_FEATURES: &[&str] = &[$( $feature, )*];
+    };
+}
+
+unstable_features! {
+    "unstable-msc1767",
+    "unstable-msc2346",
+    "unstable-msc2409",
+    "unstable-msc2448",
+    "unstable-msc2654",
+    "unstable-msc2666",
+    "unstable-msc2747",
+    "unstable-msc2867",
+    "unstable-msc2870",
+    "unstable-msc2965",
+    "unstable-msc2967",
+    "unstable-msc3061",
+    "unstable-msc3202",
+    "unstable-msc3245",
+    "unstable-msc3245-v1-compat",
+    "unstable-msc3246",
+    "unstable-msc3266",
+    "unstable-msc3381",
+    "unstable-msc3401",
+    "unstable-msc3488",
+    "unstable-msc3489",
+    "unstable-msc3551",
+    "unstable-msc3552",
+    "unstable-msc3553",
+    "unstable-msc3554",
+    "unstable-msc3575",
+    "unstable-msc3618",
+    "unstable-msc3723",
+    "unstable-msc3814",
+    "unstable-msc3843",
+    "unstable-msc3874",
+    "unstable-msc3927",
+    "unstable-msc3930",
+    "unstable-msc3931",
+    "unstable-msc3932",
+    "unstable-msc3954",
+    "unstable-msc3955",
+    "unstable-msc3956",
+    "unstable-msc3983",
+    "unstable-msc4075",
+    "unstable-msc4108",
+    "unstable-msc4121",
+    "unstable-msc4125",
+    "unstable-msc4140",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ALL_UNSTABLE_FEATURES;
+
+    /// Make sure that the list of unstable features is in sync with the cargo features.
+    #[test]
+    fn unstable_features_match_cargo_features() {
+        let manifest = include_str!("../Cargo.toml");
+
+        let mut cargo_features: Vec<_> = manifest
+            .lines()
+            .filter_map(|line| line.split_once(" = ").map(|(name, _)| name))
+            .filter(|name| name.starts_with("unstable-msc"))
+            .collect();
+
+        for feature in &cargo_features {
+            let suffix = &feature["unstable-msc".len()..];
+            let (number, extra) = suffix.split_once('-').unwrap_or((suffix, ""));
+            assert!(
+                !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()),
+                "`{feature}` doesn't follow the `unstable-mscXXXX` naming scheme"
+            );
+            assert!(
+                extra.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-'),
+                "`{feature}` doesn't follow the `unstable-mscXXXX` naming scheme"
+            );
+        }
+
+        cargo_features.sort_unstable();
+        assert_eq!(cargo_features, ALL_UNSTABLE_FEATURES);
+    }
+
+    #[test]
+    #[cfg(feature = "__unstable-mscs")]
+    fn all_unstable_features_enabled() {
+        assert!(super::unstable_features().eq(ALL_UNSTABLE_FEATURES.iter().copied()));
+    }
+}